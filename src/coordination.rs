@@ -1,221 +1,262 @@
-//! Contains the setup method for the [GameCoordinator] struct, which is meant to modify the [PlayField] state, receive & handle player input, set things up, enforce the play phases etc.
+//! Contains the setup method for the [GameCoordinator] struct, which is meant to receive & handle player input, set things up & print the state of the [Game] etc.
 //! This module holds the game loop & some auxiliary helper functions.
 
-use mill::game::{painting::*, Field};
+use std::thread;
 
+use mill::game::{painting::*, Field, Game, GamePhase};
+
+use mill::game::player::{Player, TurnAction};
+use mill::game::result::{GameResult, WinReason};
 use mill::game::state::PlayField;
 
 use mill_playfield::PlayerColor;
 use smallvec::SmallVec;
-use smartstring::alias::CompactString;
 
 mod game_phases;
+mod human;
+mod self_play;
 mod setup;
 
-#[derive(Clone, Copy)]
-pub enum GamePhase {
-    Start,
-    Set,
-    MoveAndJump,
-    Terminated,
-}
+use self_play::{Output, Session};
 
+/// The terminal front end of a [Game] between two [Player]s, which asks them for their turns & prints the play field
 pub struct GameCoordinator {
-    play_field: PlayField,
-    // 0 = Player 1, 1 = Player 2
-    player_names: (CompactString, CompactString),
-    round: u32,
-    game_phase: GamePhase,
-    // false -> Player 1, true -> Player 2
-    turn: bool,
+    game: Game,
+    // 0 = White, 1 = Black
+    players: [Box<dyn Player>; 2],
     error_state: bool,
+    session: Session,
 }
 
 impl GameCoordinator {
     // TODO Refactor in game-loop.rs
-    pub fn start_game(&mut self) {
-        let mut player_won = false;
-
-        while let GamePhase::Start = self.game_phase {
-            println!("> Starting the game!");
-            let playing_white_id = self.setup_player_colors();
-
-            // White begins: if player id is 2, set turn to 1 for player 2 to start
-            if playing_white_id {
-                self.turn = true;
-            }
-            self.round = 1;
-
-            println!(
-                "> {} plays {}.",
-                EMP.paint(format!("Player {}", self.which_players_turn())),
-                HIGHLIGHT.paint("white")
-            );
-
-            self.game_phase = GamePhase::Set;
-        }
+    /// Runs the game loop until the game terminates & returns its result
+    pub fn start_game(&mut self) -> GameResult {
+        println!("> Starting the game!");
+        println!("> {} plays {}.", EMP.paint(self.get_player_name(PlayerColor::White)), HIGHLIGHT.paint("white"));
         println!("\n> Starting with {}!", EMP.paint("Set-Phase"));
 
         let mut changes_to_highlight = SmallVec::<[Field; 3]>::new();
-        let mut set_rounds_done = 0;
 
-        while set_rounds_done < 9 {
-            let (player_color, player_name) =
-                self.print_turn_header(self.game_phase, Some(set_rounds_done), &changes_to_highlight);
+        while self.game.get_result().is_none() {
+            let is_set_phase = matches!(self.game.get_phase(), GamePhase::Set);
+            let (player_color, player_name) = self.print_turn_header(self.game.get_phase(), &changes_to_highlight);
 
             changes_to_highlight.clear();
-            let input_field = self.get_field_coord_input("> Enter a field a stone should be placed on: ");
-            changes_to_highlight.push(input_field);
-
-            match self.play_field.try_set(input_field, player_color) {
-                Ok(_) => println!(
-                    "> Successfully placed {} on {} for {}.",
-                    HIGHLIGHT.paint(player_color),
-                    HIGHLIGHT.paint(format!("{}{}", input_field.0, input_field.1)),
-                    EMP.paint(player_name)
-                ),
-                Err(err) => {
-                    print_error(&format!("{}", err));
-
+            let r#move = match self.players[Self::get_player_index(player_color)].choose_move(&self.game) {
+                TurnAction::Move(r#move) => r#move,
+                TurnAction::Undo => {
+                    self.undo_turn(&mut changes_to_highlight);
+                    continue;
+                }
+                TurnAction::Redo => {
+                    self.redo_turn(&mut changes_to_highlight);
+                    continue;
+                }
+                TurnAction::OfferDraw => {
+                    let opponent = &mut self.players[Self::get_player_index(!player_color)];
+
+                    if opponent.accept_draw(&self.game) {
+                        self.game.agree_to_draw().unwrap();
+                        break;
+                    }
+                    println!("> {} declined the draw.", EMP.paint(opponent.get_name()));
                     self.error_state = true;
                     continue;
                 }
-            }
+                TurnAction::Resign => {
+                    println!(">\n> {} resigned. Terminating game.\n>", EMP.paint(player_name.as_str()));
 
-            // If a mill ocurred & a stone was stolen, print info message & set game states according to the
-            // left amount of stones on the field. Only the opponents amount of stones changes
-            if let Some(mut mills) = self.do_mills_interaction(input_field, player_color) {
-                changes_to_highlight.append(&mut mills);
-                //for mill in mills { TODO ?
-                //if changes_to_highlight.contains(&mill) {
-                //}
-                //}
+                    self.game.resign(player_color).unwrap();
+                    break;
+                }
             };
 
-            self.error_state = false;
-            self.round += 1;
-            self.turn = !self.turn;
+            if let Err(err) = self.game.make_move(r#move.clone()) {
+                print_error(&format!("{}", err));
 
-            if let PlayerColor::Black = player_color {
-                set_rounds_done += 1;
+                self.error_state = true;
+                continue;
             }
-        }
-
-        self.game_phase = GamePhase::MoveAndJump;
-        println!("\n> Starting with {}!", EMP.paint("Move-Phase"));
 
-        while let GamePhase::MoveAndJump = self.game_phase {
-            let (player_color, player_name) = self.print_turn_header(self.game_phase, None, &changes_to_highlight);
-
-            changes_to_highlight.clear();
-            let start_field = self.get_field_coord_input("> Enter the stone you want to move: ");
-            changes_to_highlight.push(start_field);
-            let target_field = self.get_field_coord_input("> Enter it's target position: ");
-            changes_to_highlight.push(target_field);
+            // If a mill ocurred & the move doesn't specify the stones to take, the player is prompted for them
+            self.do_mills_interaction(&r#move, player_color);
 
-            // Print out the coords if move was successful, else continue loop
-            match self.play_field.try_move(start_field, target_field, player_color) {
-                Ok(_) => println!(
+            let r#move = self.game.get_last_move().expect("The move was applied after taking the stones.").clone();
+            let target_field = r#move.target_field();
+            match r#move.start_field() {
+                None => println!(
+                    "> Successfully placed {} on {} for {}.",
+                    HIGHLIGHT.paint(player_color),
+                    HIGHLIGHT.paint(format!("{}{}", target_field.0, target_field.1)),
+                    EMP.paint(&player_name)
+                ),
+                Some(start_field) => println!(
                     "> {} successfully moved a {} stone from {} to {}.",
-                    EMP.paint(player_name),
+                    EMP.paint(&player_name),
                     HIGHLIGHT.paint(player_color),
                     HIGHLIGHT.paint(format!("{}{}", start_field.0, start_field.1)),
                     HIGHLIGHT.paint(format!("{}{}", target_field.0, target_field.1))
                 ),
-                Err(err) => {
-                    print_error(&format!("{}", err));
-
-                    self.error_state = true;
-                    continue;
-                }
+            }
+            for field_taken in r#move.takes() {
+                println!("> Successfully took stone on {}", EMP.paint(format!("{}{}", field_taken.0, field_taken.1)));
             }
 
-            if let Some(mut mills) = self.do_mills_interaction(target_field, player_color) {
-                changes_to_highlight.append(&mut mills);
+            // Gives the spectators some time to follow the moves of computers
+            if let Output::Live { delay } = self.session.output {
+                if self.players[Self::get_player_index(player_color)].is_computer() {
+                    thread::sleep(delay);
+                }
             }
 
-            // The opponent of the current play might have lost a stone:
-            let player_and_amount_of_stones = match player_color {
-                PlayerColor::White => (&self.player_names.1, self.play_field.amount_of_stones.1),
-                PlayerColor::Black => (&self.player_names.0, self.play_field.amount_of_stones.0),
-            };
+            changes_to_highlight.extend(r#move.start_field());
+            changes_to_highlight.push(target_field);
+            changes_to_highlight.extend(self.game.get_position().play_field.get_mill_crossing(target_field));
 
-            // One player has less than 2 stones and has lost the game. Mutates self.phase
-            if player_and_amount_of_stones.1 <= 2 {
-                println!(
-                    ">\n> {} only has {} stones left. Terminating game.\n>",
-                    EMP.paint(player_and_amount_of_stones.0),
-                    HIGHLIGHT.paint(player_and_amount_of_stones.1)
-                );
-
-                player_won = player_and_amount_of_stones.0 != &self.player_names.0;
-                self.game_phase = GamePhase::Terminated;
-            // Info message, allowing jumps for player with only 3 stones left
-            } else if player_and_amount_of_stones.1 == 3 {
-                println!(
-                    ">\n> {} only has {} stones left. Starting with {}!\n>",
-                    EMP.paint(player_and_amount_of_stones.0),
-                    HIGHLIGHT.paint(player_and_amount_of_stones.1),
-                    EMP.paint("Jump-Phase")
-                );
-            // Normal info message printing out new amount of stones on the playfield
-            } else {
-                println!(
-                    ">\n> {} only has {} stones left.\n>",
-                    EMP.paint(player_and_amount_of_stones.0),
-                    HIGHLIGHT.paint(player_and_amount_of_stones.1),
-                );
+            self.error_state = false;
+            if is_set_phase && matches!(self.game.get_phase(), GamePhase::MoveAndJump) {
+                println!("\n> Starting with {}!", EMP.paint("Move-Phase"));
             }
 
-            self.error_state = false;
-            self.round += 1;
-            self.turn = !self.turn;
+            // The opponent of the current play might have lost a stone
+            let opponent_name = self.get_player_name(!player_color);
+            let position = self.game.get_position();
+            let amount_of_stones =
+                position.play_field.get_amount_of_stones(!player_color) + position.get_stones_in_hand(!player_color);
+            let rules = self.game.get_rules();
+
+            match self.game.get_result() {
+                Some(
+                    GameResult::WhiteWins(WinReason::TooFewStones) | GameResult::BlackWins(WinReason::TooFewStones),
+                ) => {
+                    println!(
+                        ">\n> {} only has {} stones left. Terminating game.\n>",
+                        EMP.paint(opponent_name),
+                        HIGHLIGHT.paint(amount_of_stones)
+                    );
+                }
+                Some(
+                    GameResult::WhiteWins(WinReason::NoLegalMoves) | GameResult::BlackWins(WinReason::NoLegalMoves),
+                ) => {
+                    println!(
+                        ">\n> {} can't move any of his {} stones. Terminating game.\n>",
+                        EMP.paint(opponent_name),
+                        HIGHLIGHT.paint(!player_color)
+                    );
+                }
+                // The amount of stones is part of the header in the set phase & the reason of draws is printed at the end
+                Some(_) => (),
+                None if is_set_phase => (),
+                // Info message, allowing jumps for player with only 3 stones left
+                None if rules.flying && amount_of_stones == rules.flying_stone_count => {
+                    println!(
+                        ">\n> {} only has {} stones left. Starting with {}!\n>",
+                        EMP.paint(opponent_name),
+                        HIGHLIGHT.paint(amount_of_stones),
+                        EMP.paint("Jump-Phase")
+                    );
+                }
+                // Normal info message printing out new amount of stones on the playfield
+                None => {
+                    println!(
+                        ">\n> {} only has {} stones left.\n>",
+                        EMP.paint(opponent_name),
+                        HIGHLIGHT.paint(amount_of_stones),
+                    );
+                }
+            }
         }
 
-        assert!(matches!(self.game_phase, GamePhase::Terminated));
+        assert!(matches!(self.game.get_phase(), GamePhase::Terminated));
+
+        let result = self.game.get_result().expect("The game loop only terminates after determining the result.");
 
-        let winners_name = match player_won {
-            true => &self.player_names.0,
-            false => &self.player_names.1,
-        };
-        println!("> {}", EMP.paint(format!("{} won the match! Congratulations!", winners_name)));
+        match result {
+            GameResult::WhiteWins(reason) | GameResult::BlackWins(reason) => {
+                let winners_name = self.get_player_name(result.winner().unwrap());
+                println!("> {}", EMP.paint(format!("{} won the match! Congratulations!", winners_name)));
+                println!("> {reason}. Result: {}", HIGHLIGHT.paint(result));
+            }
+            GameResult::Draw(reason) => {
+                println!("> {}", EMP.paint(format!("The match ended in a draw. {reason}.")));
+                println!("> Result: {}", HIGHLIGHT.paint(result));
+            }
+        }
 
         // TODO Ask for another round
+        result
     }
 }
 
 impl GameCoordinator {
-    /// Returns the (real, \in [0,1]) player number which currently is on turn
-    /// Turn is initially set to the player who choose the white color.
-    fn which_players_turn(&self) -> u32 {
-        (self.turn as u32) + 1
+    /// Returns a tuple which is used at the beginning of each round to display the current players name & color
+    fn get_current_turns_attributes(&self) -> (&str, PlayerColor) {
+        let side_to_move = self.game.get_side_to_move();
+        (self.get_player_name(side_to_move), side_to_move)
     }
 
-    /// Returns a tuple which is used at the beginning of each round to display the current players name & the round no
-    fn get_current_turns_attributes(&self) -> (&str, PlayerColor) {
-        match self.which_players_turn() {
-            1 => (self.player_names.0.as_str(), self.get_player_color()),
-            2 => (self.player_names.1.as_str(), self.get_player_color()),
-            _ => panic!(),
+    /// Returns the name of the player playing the specified color
+    fn get_player_name(&self, color: PlayerColor) -> &str {
+        self.players[Self::get_player_index(color)].get_name()
+    }
+
+    /// Returns the index of the player playing the specified color in [GameCoordinator::players]
+    fn get_player_index(color: PlayerColor) -> usize {
+        match color {
+            PlayerColor::White => 0,
+            PlayerColor::Black => 1,
         }
     }
 
-    /// Returns the player color of the player currently being on turn
-    fn get_player_color(&self) -> PlayerColor {
-        let current_round = self.round % 2;
+    /// Takes back the last move of the [Game], which hands the turn back to the player who made it.
+    /// Moves of computers are taken back as well, as they would be made again right away.
+    fn undo_turn(&mut self, changes_to_highlight: &mut SmallVec<[Field; 3]>) {
+        let mut undone_any = false;
+
+        while let Some((r#move, color)) = self.game.undo() {
+            println!("> Took back {} of {}.", HIGHLIGHT.paint(&r#move), HIGHLIGHT.paint(color));
+            changes_to_highlight.extend(r#move.start_field());
+            changes_to_highlight.push(r#move.target_field());
+            undone_any = true;
+
+            if !self.players[Self::get_player_index(color)].is_computer() {
+                break;
+            }
+        }
+
+        if !undone_any {
+            print_error("There is no move which could be taken back.");
+        }
+        self.error_state = !undone_any;
+    }
+
+    /// Applies the move taken back last by [undo_turn] again, as well as the moves of computers following it
+    fn redo_turn(&mut self, changes_to_highlight: &mut SmallVec<[Field; 3]>) {
+        let mut redone_any = false;
+
+        while let Some((r#move, color)) = self.game.redo() {
+            println!("> Redid {} of {}.", HIGHLIGHT.paint(&r#move), HIGHLIGHT.paint(color));
+            changes_to_highlight.extend(r#move.start_field());
+            changes_to_highlight.push(r#move.target_field());
+            redone_any = true;
+
+            if !self.players[Self::get_player_index(!color)].is_computer() {
+                break;
+            }
+        }
 
-        match current_round {
-            1 => PlayerColor::White,
-            0 => PlayerColor::Black,
-            _ => panic!(),
+        if !redone_any {
+            print_error("There is no move which could be redone.");
         }
+        self.error_state = !redone_any;
     }
 
     /// Wrapper for [print_plain] method of [PlayField], adding line breaks around it's output
     /// It is able to highlight the game field on specified points - by using the ^2 rt complexity :(
-    fn print_play_highlighted(&self, to_highlight: Option<&[Field]>) {
+    fn print_play_highlighted(play_field: &PlayField, to_highlight: Option<&[Field]>) {
         println!("\n");
-        self.play_field.print_highlighted(to_highlight);
+        play_field.print_highlighted(to_highlight);
         println!("\n");
     }
 }
@@ -1,203 +1,128 @@
-use std::io::{self, Write};
-
-use mill::game::{painting::*, Field};
+use mill::game::moves::Move;
+use mill::game::rules::TakingFromMills;
+use mill::game::state::PlayField;
+use mill::game::{painting::*, Field, GamePhase};
 use mill_playfield::PlayerColor;
 use smallvec::SmallVec;
 use smartstring::alias::CompactString;
 
-use super::{print_error, GamePhase};
+use super::print_error;
 
 impl super::GameCoordinator {
-    /// Returns valid coordinates of the game field in A_G, 1-7 mapping. The coordinate is requested after printing out the message argument
-    /// Loops & requests input until the provided input is valid. Handles ALL error cases.
+    /// Returns if mills were detected & returns them if so and prints them out.
+    /// The fields of every mill follow each other, so there are three of them for every mill. Up to three mills are
+    /// closed at once on the boards with diagonals.
+    pub fn check_for_and_get_mils(play_field: &PlayField, last_updated_field: Field) -> Option<SmallVec<[Field; 3]>> {
+        let mills = play_field.get_mill_crossing(last_updated_field);
+        if mills.is_empty() {
+            return None;
+        }
+
+        let formatted_mills = mills
+            .chunks(3)
+            .map(|mill| {
+                let fields = mill.iter().map(|field| format!("{}{}", field.0, field.1)).collect::<Vec<_>>();
+                EMP.paint(format!("({})", fields.join(", "))).to_string()
+            })
+            .collect::<Vec<_>>();
+
+        match formatted_mills.len() {
+            1 => print!("\n> Detected a mill for fields: {}!", formatted_mills[0]),
+            mill_count => print!(
+                "\n> Detected {} mills on {}!!\n> Your opponent must be sleeping, be a 3 year old, or you must be testing extreme cases ;)",
+                EMP.paint(if mill_count == 2 { "TWO" } else { "THREE" }),
+                formatted_mills.join(" and ")
+            ),
+        }
+        Some(mills)
+    }
+
+    /// Handles the mill cross-check of the last field a stone was set upon, which is done on the play field of the
+    /// pending move of the [mill::game::Game]. Includes the interaction with the [mill::game::player::Player] on turn
+    /// for selecting valid fields to take, if the [Move] itself doesn't specify the stones to take, which completes the
+    /// pending move.
+    /// The amount of stones to take & which of them are takeable is determined by the [mill::game::rules::RuleSet].
     ///
     /// Handled extreme cases:
-    /// - Input fails
-    /// - Input is to short or to long
-    /// - first char is not \in 'A'-'G'
-    /// - second char is not \in 1-7
-    pub fn get_field_coord_input(&self, message: &str) -> Field {
-        return loop {
-            print!("{}", message);
-            io::stdout().flush().unwrap();
-
-            let mut input_buffer = String::new();
-
-            match io::stdin().read_line(&mut input_buffer) {
-                Ok(_) => {
-                    let input_buffer = input_buffer.trim();
-
-                    if input_buffer.len() < 2 {
-                        print_error("Provided input is to short.");
-                        continue;
-                    } else if 3 <= input_buffer.len() {
-                        print_error("Provided input is longer than 2 characters.")
-                    }
-
-                    // Parsing checks
-                    let row_char = match input_buffer[0..1].parse::<char>() {
-                        Ok(c) if ('A'..='G').contains(&c) => c.to_uppercase().next().unwrap(),
-                        Ok(_) => {
-                            print_error("Provided input character isn't between A - G.");
-                            continue;
-                        }
-                        Err(_) => {
-                            print_error("Input does't start with a letter representing a column.");
-                            continue;
-                        }
-                    };
-                    let column_char = match input_buffer[1..2].parse::<u8>() {
-                        Ok(n) if (1..=7).contains(&n) => n,
-                        Ok(_) => {
-                            print_error("Second input char is not 1 <= && < 8.");
-                            continue;
-                        }
-                        Err(_) => {
-                            print_error("Second input char is not a number. Input is ill formatted.");
-                            continue;
-                        }
-                    };
-
-                    break (row_char, column_char);
-                }
-                Err(error) => print_error(&format!("Error occurred processing input: {error}",)),
-            }
+    /// - All stones of the opponent are element of mills, which permits taking any of them
+    /// - The opponent has no stones on the play field left which could be taken (might occur in the set phase)
+    /// - None of the opponents stones can be taken, because they are protected by mills
+    pub fn do_mills_interaction(&mut self, r#move: &Move, player_color: PlayerColor) {
+        let play_field = self.game.get_pending_play_field().unwrap_or(&self.game.get_position().play_field);
+
+        // The stones taken by the move itself are checked to fit the amount of mills when applying it
+        let Some(mills) = Self::check_for_and_get_mils(play_field, r#move.target_field()) else {
+            return;
         };
-    }
-
-    /// Returns if mills were detected & returns them if so and prints them out
-    pub fn check_for_and_get_mils(&self, last_updated_field: Field) -> Option<SmallVec<[Field; 3]>> {
-        let mills = self.play_field.get_mill_crossing(last_updated_field);
+        if !r#move.takes().is_empty() {
+            return;
+        }
+        Self::print_play_highlighted(play_field, Some(&mills));
 
-        // This hurts. And I'm not sure how to do better.
-        if mills.is_empty() {
-            None
-        } else if mills.len() == 3 {
-            let field_1 = mills[0];
-            let field_2 = mills[1];
-            let field_3 = mills[2];
-            print!(
-                "\n> Detected a mill for fields: {}!",
-                EMP.paint(format!(
-                    "({}{}, {}{}, {}{})",
-                    field_1.0, field_1.1, field_2.0, field_2.1, field_3.0, field_3.1,
-                ))
-            );
-            Some(mills)
-        } else {
-            assert!(mills.len() == 6);
-
-            let field_1 = mills[0];
-            let field_2 = mills[1];
-            let field_3 = mills[2];
-            let field_4 = mills[3];
-            let field_5 = mills[4];
-            let field_6 = mills[5];
-            print!(
-                "\n> Detected {} mills on {} and {}!!\n> Your opponent must be sleeping, be a 3 year old, or you must be testing extreme cases ;)",
-                EMP.paint("TWO"),
-                EMP.paint(format!(
-                    "({}{}, {}{}, {}{})",
-                    field_1.0, field_1.1, field_2.0, field_2.1, field_3.0, field_3.1,
-                )),
-                EMP.paint(format!(
-                    "({}{}, {}{}, {}{})",
-                    field_4.0, field_4.1, field_5.0, field_5.1, field_6.0, field_6.1,
-                ))
-            );
-            Some(mills)
+        let rules = *play_field.get_rules();
+        if let TakingFromMills::IfAllInMills = rules.taking_from_mills {
+            if play_field.all_stones_in_mills(!player_color) && 0 < self.game.get_pending_takes() {
+                println!("> All stones of your opponent are element of mills, so every one of them can be taken.");
+            }
         }
-    }
 
-    /// Handles the mill cross-check of the last field a stone was set upon.
-    /// Includes the user interaction part for selecting a valid field on the [PlayField].
-    /// Handled extreme cases:
-    /// - ~~All stones on the play field are element of mills~~
-    /// TODO This is to weak. If the player e.g. has 3 stones & all are in a mill, it must be skipped too...
-    ///
-    /// Returns true if a mill was detected for the [GamePhase] cases to trigger coordinative behavior.
-    pub fn do_mills_interaction(
-        &mut self,
-        input_field: (char, u8),
-        player_color: PlayerColor,
-    ) -> Option<SmallVec<[Field; 3]>> {
-        if let Some(mills) = self.check_for_and_get_mils(input_field) {
-            self.print_play_highlighted(Some(&mills));
-
-            // A piece of a extreme case:
-            let mut amount_of_mills = mills.len() / 3;
-            //let stones_in_mills = 0;
-            //for coord in FIELD_LUT {
-            // Every mill should be detected exactly 3 times
-            //stones_in_mills += self.play_field.get_mill_crossing(coord).len() / 3;
-            //}
-
-            //let (white_stones, black_stones) = self.play_field.amount_of_stones;
-            //let all_stones_in_mills = stones_in_mills as u32 == (white_stones + black_stones);
-
-            // While here are mill on the last set position left & not all stones are element of a mill: Prompt to take stones
-            while 0 < amount_of_mills
-            /*&& !all_stones_in_mills*/
-            {
-                let field_to_take = self.get_field_coord_input("> Enter the stone do you want to take: ");
-
-                match self.play_field.try_take(field_to_take, player_color) {
-                    Ok(_) => println!(
-                        "> Successfully took stone on {}",
-                        EMP.paint(format!("{}{}", field_to_take.0, field_to_take.1))
-                    ),
-                    Err(err) => {
-                        print_error(&format!("> Error occured taking stone: {}", err));
-                        continue;
-                    }
-                }
-
-                amount_of_mills -= 1;
+        // While here are takes for the mills left: Prompt to take stones
+        while 0 < self.game.get_pending_takes() {
+            let field_to_take = self.players[Self::get_player_index(player_color)].choose_take(&self.game);
+
+            if let Err(err) = self.game.take(field_to_take) {
+                print_error(&format!("> Error occured taking stone: {}", err));
             }
+        }
 
-            /*if all_stones_in_mills {
-                println!("> Detected as many stones on the play field as mills. There is nothing to take.");
-            }*/
+        // The game applies the move as soon as there's nothing left to take
+        let takes = self.game.get_last_move().map_or(0, |r#move| r#move.takes().len());
+        if takes < rules.takes_for_mills(mills.len() / 3) {
+            let play_field = &self.game.get_position().play_field;
 
-            Some(mills)
-        } else {
-            None
+            if play_field.get_amount_of_stones(!player_color) == 0 {
+                println!("> Your opponent has no stones on the play field left. There is nothing to take.");
+            } else {
+                println!("> All stones of your opponent are protected by mills. There is nothing to take.");
+            }
         }
     }
 
     /// Prints (depending of the state of [GameCoordinator]) out the current round, the state of the play field and messages for some phases of [GamePhase].
     /// Also skips this print outs, if the provided [error_occurred] is true.
     /// Returns some convenient values needed in the game phases for coordination of the [PlayField].
-    pub fn print_turn_header(
-        &self,
-        phase: GamePhase,
-        black_rounds_done: Option<u32>,
-        highlight: &[Field],
-    ) -> (PlayerColor, CompactString) {
+    pub fn print_turn_header(&self, phase: GamePhase, highlight: &[Field]) -> (PlayerColor, CompactString) {
         let (player_name, player_color) = self.get_current_turns_attributes();
         let player_name = CompactString::from(player_name);
 
         // Print out the round and game field info, if no error occurred
         if !self.error_state {
             println!("\n\n\t\t  ===============");
-            println!("\t\t  === {} ===", HIGHLIGHT.paint(format!("Round {}", self.round)));
+            println!("\t\t  === {} ===", HIGHLIGHT.paint(format!("Round {}", self.game.get_position().round)));
             println!("\t\t  ===============\n");
 
             if let GamePhase::Set = phase {
                 println!(
-                    "> {}, it's your turn placing a {} stone!",
+                    "> {}, it's your turn {} a {} stone!",
                     EMP.paint(player_name.as_str()),
+                    match self.game.get_rules().moving_in_set_phase {
+                        true => "placing or moving",
+                        false => "placing",
+                    },
                     HIGHLIGHT.paint(player_color)
                 );
-                let (stones_white, stones_black) = self.play_field.amount_of_stones;
+                let (stones_white, stones_black) = self.game.get_position().play_field.amount_of_stones;
                 println!(
                     "\n> Amount of stones on the playfield: {}: {}, {}: {}",
-                    EMP.paint(&self.player_names.0),
+                    EMP.paint(self.get_player_name(PlayerColor::White)),
                     HIGHLIGHT.paint(stones_white),
-                    EMP.paint(&self.player_names.1),
+                    EMP.paint(self.get_player_name(PlayerColor::Black)),
                     HIGHLIGHT.paint(stones_black)
                 );
-                println!("> Stones left to set: {}", HIGHLIGHT.paint(9 - black_rounds_done.unwrap()));
+                println!(
+                    "> Stones left to set: {}",
+                    HIGHLIGHT.paint(self.game.get_position().get_stones_in_hand(player_color))
+                );
             } else if let GamePhase::MoveAndJump = phase {
                 println!(
                     "> {}, it's your turn making a move with {}!",
@@ -207,11 +132,33 @@ impl super::GameCoordinator {
             }
 
             if !highlight.is_empty() {
-                self.print_play_highlighted(Some(highlight));
+                Self::print_play_highlighted(&self.game.get_position().play_field, Some(highlight));
             } else {
-                self.print_play_highlighted(None);
+                Self::print_play_highlighted(&self.game.get_position().play_field, None);
             }
         }
         (player_color, player_name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mill::game::rules::RuleSet;
+    use mill::game::state::PlayField;
+    use mill_playfield::PlayerColor;
+
+    use crate::coordination::GameCoordinator;
+
+    #[test]
+    fn triple_mills_are_detected() {
+        // B2 closes the mills on the bottom square, the left column & the diagonal of the twelve men's morris board
+        let mut play_field = PlayField::with_rules(RuleSet::twelve_mens_morris());
+        for field in [('D', 2), ('F', 2), ('B', 4), ('B', 6), ('A', 1), ('C', 3), ('B', 2)] {
+            play_field.try_set(field, PlayerColor::White).unwrap();
+        }
+
+        let mills = GameCoordinator::check_for_and_get_mils(&play_field, ('B', 2)).unwrap();
+        assert_eq!(mills.len(), 9);
+        assert_eq!(mills.chunks(3).filter(|mill| mill.contains(&('B', 2))).count(), 3);
+    }
+}
@@ -0,0 +1,121 @@
+//! Contains the [HumanPlayer], a [Player] entering his moves at the terminal.
+
+use std::io::{self, Write};
+
+use mill::game::moves::{parse_field, Move};
+use mill::game::player::{Player, TurnAction};
+use mill::game::{painting::*, Field, Game};
+use smartstring::alias::CompactString;
+
+use super::print_error;
+
+pub struct HumanPlayer {
+    name: CompactString,
+}
+
+impl HumanPlayer {
+    pub fn new(name: &str) -> Self {
+        HumanPlayer { name: CompactString::from(name) }
+    }
+
+    /// Returns valid coordinates of the game field in A_G, 1-7 mapping. The coordinate is requested after printing out the message argument
+    /// Loops & requests input until the provided input is valid. Handles ALL error cases.
+    ///
+    /// Handled extreme cases:
+    /// - Input fails
+    /// - Input can't be parsed by [parse_field]
+    fn get_field_coord_input(&self, message: &str) -> Field {
+        loop {
+            print!("{}", message);
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) => match parse_field(input_buffer.trim()) {
+                    Ok(field) => break field,
+                    Err(err) => print_error(&format!("{}", err)),
+                },
+                Err(error) => print_error(&format!("Error occurred processing input: {error}",)),
+            }
+        }
+    }
+
+    /// Returns a [Move] in the notation of [mill::game::moves], which is requested after printing out the message argument.
+    /// Loops & requests input until a move of a permitted kind was entered: A placement while there are stones left in
+    /// the players hand, a movement otherwise or if the rules allow moving in the set phase.
+    /// Alternatively, the player might enter `undo`, `redo`, `draw` to offer a draw or `resign`.
+    ///
+    /// Handled extreme cases:
+    /// - Input fails
+    /// - Input can't be parsed as [Move]
+    /// - A movement is entered although only placements are permitted or the other way around
+    fn get_move_input(&self, message: &str, can_place: bool, can_move: bool) -> TurnAction {
+        loop {
+            print!("{}", message);
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) if input_buffer.trim().eq_ignore_ascii_case("undo") => break TurnAction::Undo,
+                Ok(_) if input_buffer.trim().eq_ignore_ascii_case("redo") => break TurnAction::Redo,
+                Ok(_) if input_buffer.trim().eq_ignore_ascii_case("draw") => break TurnAction::OfferDraw,
+                Ok(_) if input_buffer.trim().eq_ignore_ascii_case("resign") => break TurnAction::Resign,
+                Ok(_) => match input_buffer.parse::<Move>() {
+                    Ok(r#move) if matches!(r#move, Move::Place { .. }) && can_place => break TurnAction::Move(r#move),
+                    Ok(r#move) if !matches!(r#move, Move::Place { .. }) && can_move => break TurnAction::Move(r#move),
+                    Ok(_) if can_place => print_error("Stones must be placed in the set phase, e.g. d1."),
+                    Ok(_) => print_error("Stones must be moved from one field to another, e.g. a1-d1."),
+                    Err(err) => print_error(&format!("{}", err)),
+                },
+                Err(error) => print_error(&format!("Error occurred processing input: {error}",)),
+            }
+        }
+    }
+}
+
+impl Player for HumanPlayer {
+    fn get_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Prompts for a move of a kind permitted in the current phase
+    fn choose_move(&mut self, game: &Game) -> TurnAction {
+        // Stones are placed while they are left in the hand. Depending on the rules, they might be moved as well.
+        let can_place = 0 < game.get_position().get_stones_in_hand(game.get_side_to_move());
+        let can_move = !can_place || game.get_rules().moving_in_set_phase;
+
+        let requested_move = match (can_place, can_move) {
+            (true, false) => "a field a stone should be placed on (e.g. d1 or d1xg7)",
+            (false, _) => "the stone you want to move & it's target (e.g. a1-d1)",
+            (true, true) => "a field to place a stone on (e.g. d1) or a stone to move & it's target (e.g. a1-d1)",
+        };
+        let message = format!("> Enter {requested_move}, undo, redo, draw or resign: ");
+        self.get_move_input(&message, can_place, can_move)
+    }
+
+    fn choose_take(&mut self, _game: &Game) -> Field {
+        self.get_field_coord_input("> Enter the stone do you want to take: ")
+    }
+
+    /// Asks the player if he accepts the offered draw.
+    /// Loops & requests input until the answer is either yes or no.
+    fn accept_draw(&mut self, _game: &Game) -> bool {
+        loop {
+            print!("> {}, do you accept the offered draw? (yes/no): ", EMP.paint(self.name.as_str()));
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) => match input_buffer.trim().to_ascii_lowercase().as_str() {
+                    "yes" | "y" => break true,
+                    "no" | "n" => break false,
+                    _ => print_error("Please answer with yes or no."),
+                },
+                Err(error) => print_error(&format!("Error occurred processing input: {error}",)),
+            }
+        }
+    }
+}
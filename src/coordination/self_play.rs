@@ -0,0 +1,135 @@
+//! Contains the session of the [GameCoordinator], which runs one game or a batch of games between two computers, and
+//! the [GameSummary] printed after games played by computers only.
+
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use mill::game::player::play_game;
+use mill::game::result::GameResult;
+use mill::game::state::PlayField;
+use mill::game::{painting::*, Game};
+use mill_playfield::PlayerColor;
+
+use super::GameCoordinator;
+
+/// How the games of a session are shown
+#[derive(Clone, Copy, Debug)]
+pub enum Output {
+    /// Every turn is printed, pausing for the delay after every move of a computer
+    Live { delay: Duration },
+    /// Only the summary of every game is printed
+    Headless,
+}
+
+/// The amount of games played in a row & how they are shown
+#[derive(Clone, Copy, Debug)]
+pub struct Session {
+    pub games: u32,
+    pub output: Output,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session { games: 1, output: Output::Live { delay: Duration::ZERO } }
+    }
+}
+
+/// The key figures of a terminated game
+pub struct GameSummary {
+    pub result: GameResult,
+    pub moves: usize,
+    // first one: white, second one: black
+    pub mills_closed: (u32, u32),
+}
+
+impl GameSummary {
+    /// Replays the moves of the game to count the mills closed by them, which are more than the moves taking stones
+    /// if there was nothing left to take
+    pub fn of(game: &Game) -> Self {
+        let history = game.get_position().play_field.get_history();
+        let mut replay = PlayField::with_rules(*game.get_rules());
+        let mut mills_closed = (0, 0);
+
+        for (r#move, color) in history {
+            let mut simulation = replay.clone();
+            simulation.try_set_or_move(r#move, *color).unwrap();
+            let mills = simulation.get_mill_crossing(r#move.target_field()).len() as u32 / 3;
+
+            match color {
+                PlayerColor::White => mills_closed.0 += mills,
+                PlayerColor::Black => mills_closed.1 += mills,
+            }
+            replay.apply_move(r#move, *color).unwrap();
+        }
+
+        GameSummary {
+            result: game.get_result().expect("Only terminated games are summarized."),
+            moves: history.len(),
+            mills_closed,
+        }
+    }
+}
+
+impl Display for GameSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "{} after {} moves, mills closed by white: {}, by black: {}",
+            self.result, self.moves, self.mills_closed.0, self.mills_closed.1
+        ))
+    }
+}
+
+impl GameCoordinator {
+    /// Runs the games of the session & returns the result of the last one.
+    /// Games played by computers only are summarized after they terminated & the whole session at its end.
+    pub fn start_session(&mut self) -> GameResult {
+        let computers_only = self.players.iter().all(|player| player.is_computer());
+        let rules = *self.game.get_rules();
+        let mut summaries = Vec::new();
+
+        for number in 1..=self.session.games {
+            self.game = Game::new(rules);
+
+            let result = match self.session.output {
+                Output::Live { .. } => self.start_game(),
+                Output::Headless => play_game(&mut self.game, &mut self.players),
+            };
+            if !computers_only {
+                return result;
+            }
+
+            let summary = GameSummary::of(&self.game);
+            println!("> Game {}: {}", HIGHLIGHT.paint(number), summary);
+            summaries.push(summary);
+        }
+
+        Self::print_session_summary(&summaries);
+        summaries.last().expect("Sessions consist of at least one game.").result
+    }
+
+    fn print_session_summary(summaries: &[GameSummary]) {
+        let count = |won: fn(&GameResult) -> bool| summaries.iter().filter(|summary| won(&summary.result)).count();
+        let white_wins = count(|result| matches!(result, GameResult::WhiteWins(_)));
+        let black_wins = count(|result| matches!(result, GameResult::BlackWins(_)));
+        let draws = count(|result| matches!(result, GameResult::Draw(_)));
+
+        let games = summaries.len();
+        let moves = summaries.iter().map(|summary| summary.moves).sum::<usize>();
+        let mills_closed = summaries.iter().map(|summary| summary.mills_closed.0 + summary.mills_closed.1).sum::<u32>();
+
+        println!("\n> {}", EMP.paint(format!("Summary of {games} game(s)")));
+        println!(
+            "> White won {}, black won {}, {} ended in a draw.",
+            HIGHLIGHT.paint(white_wins),
+            HIGHLIGHT.paint(black_wins),
+            HIGHLIGHT.paint(draws)
+        );
+        println!(
+            "> Moves: {} ({:.1} per game), mills closed: {} ({:.1} per game)",
+            HIGHLIGHT.paint(moves),
+            moves as f64 / games as f64,
+            HIGHLIGHT.paint(mills_closed),
+            mills_closed as f64 / games as f64
+        );
+    }
+}
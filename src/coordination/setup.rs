@@ -1,15 +1,94 @@
+use std::fmt::{self, Display};
 use std::io::{self, Write};
+use std::time::Duration;
 
-use mill::game::{painting::*, state::PlayField};
+use mill::game::engine::evaluation::Weights;
+use mill::game::engine::mcts::{MctsBudget, MctsConfig};
+use mill::game::player::computer::{ComputerPlayer, Strength};
+use mill::game::player::mcts::MctsPlayer;
+use mill::game::rules::{DrawConditions, RuleSet};
+use mill::game::{painting::*, player::Player, Game};
+use mill_playfield::PlayerColor;
 
 use crate::coordination::print_error;
 
-use super::{GameCoordinator, GamePhase};
+use super::human::HumanPlayer;
+use super::self_play::{Output, Session};
+use super::GameCoordinator;
+
+/// The opponent chosen by the first player, who plays alone if the opponent is a computer
+#[derive(Clone, Copy)]
+enum Opponent {
+    Human,
+    Computer(Computer),
+    // Two computers play against each other
+    Nobody,
+}
+
+/// The computer players, which either search the moves a few turns ahead or play them out by a Monte Carlo tree
+/// search for a second
+#[derive(Clone, Copy)]
+enum Computer {
+    /// The weights are the ones the expert evaluates positions by, which are the default ones if there are none
+    Searching(Strength, Option<Weights>),
+    MonteCarlo,
+}
+
+/// The computers to choose from in the menus, along with their name & description
+const COMPUTERS: [(&str, &str, Computer); 5] = [
+    ("Beginner", "looking at the outcome of its own move only", Computer::Searching(Strength::Beginner, None)),
+    ("Intermediate", "looking at the replies to its moves", Computer::Searching(Strength::Intermediate, None)),
+    ("Advanced", "looking three turns ahead", Computer::Searching(Strength::Advanced, None)),
+    (
+        "Expert",
+        "searching a second for every move on the Nine Men's Morris board",
+        Computer::Searching(Strength::Expert, None),
+    ),
+    (
+        "Monte Carlo",
+        "playing out a second of games for every move on the Nine Men's Morris board",
+        Computer::MonteCarlo,
+    ),
+];
+
+impl Computer {
+    fn create_player(self, name: &str) -> Box<dyn Player> {
+        match self {
+            Computer::Searching(strength, weights) => {
+                let mut player = ComputerPlayer::new(name, strength);
+                if let Some(weights) = weights {
+                    player.set_weights(weights);
+                }
+                Box::new(player)
+            }
+            Computer::MonteCarlo => {
+                let budget = MctsBudget::Time(Duration::from_secs(1));
+                Box::new(MctsPlayer::new(name, MctsConfig { budget, ..Default::default() }))
+            }
+        }
+    }
+}
+
+impl Display for Computer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Computer::Searching(strength, _) => strength.fmt(f),
+            Computer::MonteCarlo => f.write_str("Monte Carlo"),
+        }
+    }
+}
 
 impl GameCoordinator {
     pub fn setup() -> Self {
+        let computer = match Self::setup_opponent() {
+            Opponent::Human => None,
+            Opponent::Computer(computer) => Some(Self::setup_weights(computer)),
+            Opponent::Nobody => return Self::setup_self_play(),
+        };
+        println!();
+
         let mut player_1: Option<String> = None;
-        let player_2: Option<String>;
+        let mut player_2: Option<String> = None;
         let mut current_player_assigned_to = 1;
 
         loop {
@@ -35,6 +114,11 @@ impl GameCoordinator {
 
                         // .clone().unwrap() looks like bad library design for me...
                         println!("> Here we go, {}!", EMP.paint(player_1.clone().unwrap()));
+
+                        // The computer is the second player
+                        if computer.is_some() {
+                            break;
+                        }
                     } else {
                         if player_1.clone().unwrap() == input_buffer {
                             print_error("Player are the same.");
@@ -51,28 +135,206 @@ impl GameCoordinator {
         }
         println!();
 
+        let rules = Self::setup_rules();
+        println!();
+
+        let player_1 = player_1.unwrap();
+        let player_2: Box<dyn Player> = match computer {
+            Some(computer) => computer.create_player(&format!("Computer ({computer})")),
+            None => Box::new(HumanPlayer::new(&player_2.unwrap())),
+        };
+        let player_2_plays_white = Self::setup_player_colors((&player_1, player_2.get_name()));
+        println!();
+
+        let mut players: [Box<dyn Player>; 2] = [Box::new(HumanPlayer::new(&player_1)), player_2];
+        if player_2_plays_white {
+            players.swap(0, 1);
+        }
+
         GameCoordinator {
-            play_field: PlayField::default(),
-            player_names: (
-                smartstring::alias::CompactString::from(player_1.unwrap()),
-                smartstring::alias::CompactString::from(player_2.unwrap()),
-            ),
-            round: 0,
-            game_phase: GamePhase::Start,
-            turn: false,
+            game: Game::new(rules),
+            players,
             error_state: false,
+            session: Session::default(),
+        }
+    }
+
+    /// Sets up a session of two computers playing against each other.
+    /// Rules without draw conditions get the ones of the tournament rules, as the games might never end otherwise.
+    fn setup_self_play() -> Self {
+        println!();
+        let computers = [PlayerColor::White, PlayerColor::Black].map(|color| {
+            let computer = Self::setup_weights(Self::setup_computer(color));
+            println!();
+            computer
+        });
+
+        let mut rules = Self::setup_rules();
+        if rules.draw_conditions == DrawConditions::default() {
+            rules.draw_conditions = RuleSet::tournament().draw_conditions;
+            println!(
+                "> Games are drawn on repetition & after 50 moves without a mill, as computers might never end them."
+            );
+        }
+        println!();
+
+        let session = Self::setup_session();
+        println!();
+
+        let players = [PlayerColor::White, PlayerColor::Black].map(|color| {
+            let computer = computers[GameCoordinator::get_player_index(color)];
+            let name = match color {
+                PlayerColor::White => format!("White computer ({computer})"),
+                PlayerColor::Black => format!("Black computer ({computer})"),
+            };
+            computer.create_player(&name)
+        });
+
+        GameCoordinator { game: Game::new(rules), players, error_state: false, session }
+    }
+
+    /// Returns who the player wants to play against
+    fn setup_opponent() -> Opponent {
+        let computers =
+            COMPUTERS.map(|(name, description, computer)| (name, description, Opponent::Computer(computer)));
+        let opponents = [
+            &[("Human", "another player at this terminal", Opponent::Human)][..],
+            &computers,
+            &[(
+                "Nobody",
+                "watch two computers play against each other or run a batch of their games",
+                Opponent::Nobody,
+            )],
+        ]
+        .concat();
+
+        println!("> Who do you want to play against?");
+        Self::get_menu_input(&opponents, "your opponent")
+    }
+
+    /// Returns the computer playing the specified color
+    fn setup_computer(color: PlayerColor) -> Computer {
+        println!("> Which computer should play {}?", HIGHLIGHT.paint(color));
+        Self::get_menu_input(&COMPUTERS, "the computer")
+    }
+
+    /// Returns the computer with the weights of the expert read from the config file entered, see [Weights].
+    /// The other computers are returned as they are.
+    fn setup_weights(computer: Computer) -> Computer {
+        let Computer::Searching(Strength::Expert, _) = computer else {
+            return computer;
+        };
+
+        loop {
+            print!("> Enter the {} of the expert's weights or nothing for the default ones: ", EMP.paint("file"));
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) if input_buffer.trim().is_empty() => break computer,
+                Ok(_) => match Weights::from_file(input_buffer.trim()) {
+                    Ok(weights) => break Computer::Searching(Strength::Expert, Some(weights)),
+                    Err(error) => print_error(&error.to_string()),
+                },
+                Err(error) => print_error(&format!(
+                    "> Error processing input: {}
+",
+                    error
+                )),
+            }
+        }
+    }
+
+    /// Returns the [RuleSet] the players agreed on, which is one of its presets
+    fn setup_rules() -> RuleSet {
+        let presets = [
+            ("Standard", "flying with 3 stones, two stones taken on double mills", RuleSet::default()),
+            (
+                "Tournament",
+                "one stone taken on double mills, draws on repetition & after 50 moves",
+                RuleSet::tournament(),
+            ),
+            ("Without flying", "stones only slide along the lines until the game ends", RuleSet::without_flying()),
+            ("Lasker", "10 stones, which might be moved before all of them are placed", RuleSet::lasker()),
+            ("Twelve Men's Morris", "12 stones on the board with diagonals, no flying", RuleSet::twelve_mens_morris()),
+            ("Morabaraba", "12 stones on the board with diagonals, flying with 3 stones", RuleSet::morabaraba()),
+            ("Three Men's Morris", "3 stones on the smallest board, the first mill wins", RuleSet::three_mens_morris()),
+            ("Six Men's Morris", "6 stones on the board of two squares, no flying", RuleSet::six_mens_morris()),
+        ];
+
+        println!("> Which rules do you want to play with?");
+        Self::get_menu_input(&presets, "the rules")
+    }
+
+    /// Returns the amount of games two computers play in a row & if they're shown live with a delay between the moves
+    fn setup_session() -> Session {
+        let games = loop {
+            print!("> How many {} should be played? ", EMP.paint("games"));
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) => match input_buffer.trim().parse::<u32>() {
+                    Ok(games) if 0 < games => break games,
+                    _ => print_error("Input must be a number greater than 0."),
+                },
+                Err(error) => print_error(&format!("> Error processing input: {}\n", error)),
+            }
+        };
+
+        let output = loop {
+            print!(
+                "> Enter the {} between the moves in milliseconds or {} to only print the results: ",
+                EMP.paint("delay"),
+                EMP.paint("headless")
+            );
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) if input_buffer.trim().eq_ignore_ascii_case("headless") => break Output::Headless,
+                Ok(_) => match input_buffer.trim().parse::<u64>() {
+                    Ok(delay) => break Output::Live { delay: Duration::from_millis(delay) },
+                    _ => print_error("Input must either be a number of milliseconds or headless."),
+                },
+                Err(error) => print_error(&format!("> Error processing input: {}\n", error)),
+            }
+        };
+
+        Session { games, output }
+    }
+
+    /// Prints the numbered entries of the menu & returns the value of the entry chosen by the player.
+    /// Loops & requests input until the number of an entry was entered.
+    fn get_menu_input<T: Copy>(entries: &[(&str, &str, T)], subject: &str) -> T {
+        for (number, (name, description, _)) in entries.iter().enumerate() {
+            println!(">   {}: {} - {}", HIGHLIGHT.paint(number + 1), EMP.paint(name), description);
+        }
+
+        loop {
+            print!("> Please enter the {} of {}: ", EMP.paint("number"), subject);
+            io::stdout().flush().unwrap();
+
+            let mut input_buffer = String::new();
+            match io::stdin().read_line(&mut input_buffer) {
+                Ok(_) => match input_buffer.trim().parse::<usize>() {
+                    Ok(number) if (1..=entries.len()).contains(&number) => break entries[number - 1].2,
+                    _ => print_error(&format!("Input must be a number between 1 and {}.", entries.len())),
+                },
+                Err(error) => print_error(&format!("> Error processing input: {}\n", error)),
+            }
         }
     }
 
     /// Returns the player playing white, coded as 0 for player 1, 1 for player 2
-    pub fn setup_player_colors(&self) -> bool {
+    fn setup_player_colors(player_names: (&str, &str)) -> bool {
         let error_message = "Input must either be 1, 2 or a players name. Please try again.";
 
         return loop {
             println!(
                 "> Which player wants to play with the {} >>{}<<?",
                 HIGHLIGHT.paint("white stones"),
-                HIGHLIGHT.paint(mill_playfield::PlayerColor::White)
+                HIGHLIGHT.paint(PlayerColor::White)
             );
             print!("> Please enter a {} or the {}: ", EMP.paint("players name"), EMP.paint("player's number"));
             io::stdout().flush().unwrap();
@@ -82,10 +344,10 @@ impl GameCoordinator {
                 Ok(_) => {
                     let input_buffer = input_buffer.trim();
                     // Player 0/ 1 shall play white
-                    if input_buffer == self.player_names.0 {
+                    if input_buffer == player_names.0 {
                         break false;
                     // Player 1/ 2 shall play white
-                    } else if input_buffer == self.player_names.1 {
+                    } else if input_buffer == player_names.1 {
                         break true;
                     } else if let Ok(int) = input_buffer.parse::<i32>() {
                         if !(1..3).contains(&int) {
@@ -0,0 +1,368 @@
+//! Holds the topology of the play field: The points stones can be placed upon, the lines connecting them & the mills.
+//! Everything in here is modelled as static tables, which are indexed by the index of a point in [Board::points].
+//! This index equals the index of the [crate::game::state::PlayField]s internal state array.
+//!
+//! The [Board]s of the variants bundle these tables. The one played on is chosen by the
+//! [crate::game::rules::RuleSet].
+
+use crate::game::Field;
+
+pub const POINT_COUNT: usize = 24;
+pub const MILL_COUNT: usize = 16;
+
+/// The topology of a play field, on which one of the variants of the game is played
+#[derive(PartialEq, Eq, Hash, Debug)]
+pub struct Board {
+    pub name: &'static str,
+    pub points: &'static [Field],
+    pub neighbors: &'static [&'static [usize]],
+    pub mills: &'static [[usize; 3]],
+    pub ascii_template: &'static [&'static str],
+    /// The column letters printed below the [Board::ascii_template], aligned to its points
+    pub column_labels: &'static str,
+}
+
+/// The board of the classic game, three squares connected by lines in their middles
+pub static NINE_MENS_MORRIS: Board = Board {
+    name: "Nine Men's Morris",
+    points: &POINTS,
+    neighbors: &NEIGHBORS,
+    mills: &MILLS,
+    ascii_template: &ASCII_TEMPLATE,
+    column_labels: COLUMN_LABELS,
+};
+
+/// The board of Twelve Men's Morris & Morabaraba, which additionally connects the corners of the squares diagonally
+pub static TWELVE_MENS_MORRIS: Board = Board {
+    name: "Twelve Men's Morris",
+    points: &POINTS,
+    neighbors: &DIAGONAL_NEIGHBORS,
+    mills: &DIAGONAL_MILLS,
+    ascii_template: &DIAGONAL_ASCII_TEMPLATE,
+    column_labels: COLUMN_LABELS,
+};
+
+/// The smallest board, a square divided into four connected by lines through its middle & diagonals
+pub static THREE_MENS_MORRIS: Board = Board {
+    name: "Three Men's Morris",
+    points: &THREE_MENS_POINTS,
+    neighbors: &THREE_MENS_NEIGHBORS,
+    mills: &THREE_MENS_MILLS,
+    ascii_template: &THREE_MENS_ASCII_TEMPLATE,
+    column_labels: "A      B      C",
+};
+
+/// The board of Nine Men's Morris without its inner square
+pub static SIX_MENS_MORRIS: Board = Board {
+    name: "Six Men's Morris",
+    points: &SIX_MENS_POINTS,
+    neighbors: &SIX_MENS_NEIGHBORS,
+    mills: &SIX_MENS_MILLS,
+    ascii_template: &SIX_MENS_ASCII_TEMPLATE,
+    column_labels: "A   B   C   D   E",
+};
+
+/// All points of the play field, ordered row by row from the bottom left to the top right.
+#[rustfmt::skip]
+pub const POINTS: [Field; POINT_COUNT] = [
+    ('A',1), ('D',1), ('G',1),
+    ('B',2), ('D',2), ('F',2),
+    ('C',3), ('D',3), ('E',3),
+    ('A',4), ('B',4), ('C',4), ('E',4), ('F',4), ('G',4),
+    ('C',5), ('D',5), ('E',5),
+    ('B',6), ('D',6), ('F',6),
+    ('A',7), ('D',7), ('G',7),
+];
+
+/// The adjacency list of every point, which are the points a stone can slide to in the move phase.
+#[rustfmt::skip]
+pub const NEIGHBORS: [&[usize]; POINT_COUNT] = [
+    &[1, 9],           // A1
+    &[0, 2, 4],        // D1
+    &[1, 14],          // G1
+    &[4, 10],          // B2
+    &[1, 3, 5, 7],     // D2
+    &[4, 13],          // F2
+    &[7, 11],          // C3
+    &[4, 6, 8],        // D3
+    &[7, 12],          // E3
+    &[0, 10, 21],      // A4
+    &[3, 9, 11, 18],   // B4
+    &[6, 10, 15],      // C4
+    &[8, 13, 17],      // E4
+    &[5, 12, 14, 20],  // F4
+    &[2, 13, 23],      // G4
+    &[11, 16],         // C5
+    &[15, 17, 19],     // D5
+    &[12, 16],         // E5
+    &[10, 19],         // B6
+    &[16, 18, 20, 22], // D6
+    &[13, 19],         // F6
+    &[9, 22],          // A7
+    &[19, 21, 23],     // D7
+    &[14, 22],         // G7
+];
+
+/// All lines of three points forming a mill. The horizontal ones come first, ordered from bottom to top,
+/// followed by the vertical ones ordered from left to right.
+#[rustfmt::skip]
+pub const MILLS: [[usize; 3]; MILL_COUNT] = [
+    [0, 1, 2], [3, 4, 5], [6, 7, 8], [9, 10, 11],
+    [12, 13, 14], [15, 16, 17], [18, 19, 20], [21, 22, 23],
+    [0, 9, 21], [3, 10, 18], [6, 11, 15], [1, 4, 7],
+    [16, 19, 22], [8, 12, 17], [5, 13, 20], [2, 14, 23],
+];
+
+/// The adjacency list of every point of the [TWELVE_MENS_MORRIS] board, including the diagonals.
+#[rustfmt::skip]
+pub const DIAGONAL_NEIGHBORS: [&[usize]; POINT_COUNT] = [
+    &[1, 3, 9],           // A1
+    &[0, 2, 4],           // D1
+    &[1, 5, 14],          // G1
+    &[0, 4, 6, 10],       // B2
+    &[1, 3, 5, 7],        // D2
+    &[2, 4, 8, 13],       // F2
+    &[3, 7, 11],          // C3
+    &[4, 6, 8],           // D3
+    &[5, 7, 12],          // E3
+    &[0, 10, 21],         // A4
+    &[3, 9, 11, 18],      // B4
+    &[6, 10, 15],         // C4
+    &[8, 13, 17],         // E4
+    &[5, 12, 14, 20],     // F4
+    &[2, 13, 23],         // G4
+    &[11, 16, 18],        // C5
+    &[15, 17, 19],        // D5
+    &[12, 16, 20],        // E5
+    &[10, 15, 19, 21],    // B6
+    &[16, 18, 20, 22],    // D6
+    &[13, 17, 19, 23],    // F6
+    &[9, 18, 22],         // A7
+    &[19, 21, 23],        // D7
+    &[14, 20, 22],        // G7
+];
+
+/// The mills of the [TWELVE_MENS_MORRIS] board, which are the ones of [MILLS] followed by the diagonals
+/// from the bottom left to the top right.
+#[rustfmt::skip]
+pub const DIAGONAL_MILLS: [[usize; 3]; MILL_COUNT + 4] = [
+    [0, 1, 2], [3, 4, 5], [6, 7, 8], [9, 10, 11],
+    [12, 13, 14], [15, 16, 17], [18, 19, 20], [21, 22, 23],
+    [0, 9, 21], [3, 10, 18], [6, 11, 15], [1, 4, 7],
+    [16, 19, 22], [8, 12, 17], [5, 13, 20], [2, 14, 23],
+    [0, 3, 6], [2, 5, 8], [15, 18, 21], [17, 20, 23],
+];
+
+/// The play field as it's printed out on the terminal, from the top row (7) to the bottom one (1).
+/// Every `@` is a placeholder for a point, which are filled in from left to right.
+/// Lines with odd indices are the connections between two rows.
+#[rustfmt::skip]
+pub const ASCII_TEMPLATE: [&str; 13] = [
+    "@------------@------------@",
+    "|            |            |",
+    "|   @--------@--------@   |",
+    "|   |        |        |   |",
+    "|   |   @----@----@   |   |",
+    "|   |   |         |   |   |",
+    "@---@---@         @---@---@",
+    "|   |   |         |   |   |",
+    "|   |   @----@----@   |   |",
+    "|   |        |        |   |",
+    "|   @--------@--------@   |",
+    "|            |            |",
+    "@------------@------------@",
+];
+
+const COLUMN_LABELS: &str = "A   B   C    D    E   F   G";
+
+/// The [ASCII_TEMPLATE] including the diagonals of the [TWELVE_MENS_MORRIS] board.
+#[rustfmt::skip]
+pub const DIAGONAL_ASCII_TEMPLATE: [&str; 13] = [
+    "@------------@------------@",
+    "| \\          |          / |",
+    "|   @--------@--------@   |",
+    "|   | \\      |      / |   |",
+    "|   |   @----@----@   |   |",
+    "|   |   |         |   |   |",
+    "@---@---@         @---@---@",
+    "|   |   |         |   |   |",
+    "|   |   @----@----@   |   |",
+    "|   | /      |      \\ |   |",
+    "|   @--------@--------@   |",
+    "| /          |          \\ |",
+    "@------------@------------@",
+];
+
+#[rustfmt::skip]
+pub const THREE_MENS_POINTS: [Field; 9] = [
+    ('A',1), ('B',1), ('C',1),
+    ('A',2), ('B',2), ('C',2),
+    ('A',3), ('B',3), ('C',3),
+];
+
+#[rustfmt::skip]
+pub const THREE_MENS_NEIGHBORS: [&[usize]; 9] = [
+    &[1, 3, 4],                   // A1
+    &[0, 2, 4],                   // B1
+    &[1, 4, 5],                   // C1
+    &[0, 4, 6],                   // A2
+    &[0, 1, 2, 3, 5, 6, 7, 8],    // B2
+    &[2, 4, 8],                   // C2
+    &[3, 4, 7],                   // A3
+    &[4, 6, 8],                   // B3
+    &[4, 5, 7],                   // C3
+];
+
+/// The rows, followed by the columns & the diagonals
+#[rustfmt::skip]
+pub const THREE_MENS_MILLS: [[usize; 3]; 8] = [
+    [0, 1, 2], [3, 4, 5], [6, 7, 8],
+    [0, 3, 6], [1, 4, 7], [2, 5, 8],
+    [0, 4, 8], [2, 4, 6],
+];
+
+#[rustfmt::skip]
+pub const THREE_MENS_ASCII_TEMPLATE: [&str; 5] = [
+    "@------@------@",
+    "| \\    |    / |",
+    "@------@------@",
+    "| /    |    \\ |",
+    "@------@------@",
+];
+
+#[rustfmt::skip]
+pub const SIX_MENS_POINTS: [Field; 16] = [
+    ('A',1), ('C',1), ('E',1),
+    ('B',2), ('C',2), ('D',2),
+    ('A',3), ('B',3), ('D',3), ('E',3),
+    ('B',4), ('C',4), ('D',4),
+    ('A',5), ('C',5), ('E',5),
+];
+
+#[rustfmt::skip]
+pub const SIX_MENS_NEIGHBORS: [&[usize]; 16] = [
+    &[1, 6],           // A1
+    &[0, 2, 4],        // C1
+    &[1, 9],           // E1
+    &[4, 7],           // B2
+    &[1, 3, 5],        // C2
+    &[4, 8],           // D2
+    &[0, 7, 13],       // A3
+    &[3, 6, 10],       // B3
+    &[5, 9, 12],       // D3
+    &[2, 8, 15],       // E3
+    &[7, 11],          // B4
+    &[10, 12, 14],     // C4
+    &[8, 11],          // D4
+    &[6, 14],          // A5
+    &[11, 13, 15],     // C5
+    &[9, 14],          // E5
+];
+
+/// The sides of both squares, the horizontal ones first
+#[rustfmt::skip]
+pub const SIX_MENS_MILLS: [[usize; 3]; 8] = [
+    [0, 1, 2], [3, 4, 5], [10, 11, 12], [13, 14, 15],
+    [0, 6, 13], [3, 7, 10], [5, 8, 12], [2, 9, 15],
+];
+
+#[rustfmt::skip]
+pub const SIX_MENS_ASCII_TEMPLATE: [&str; 9] = [
+    "@-------@-------@",
+    "|       |       |",
+    "|   @---@---@   |",
+    "|   |       |   |",
+    "@---@       @---@",
+    "|   |       |   |",
+    "|   @---@---@   |",
+    "|       |       |",
+    "@-------@-------@",
+];
+
+impl Board {
+    pub fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the index of the specified field in [Board::points], if the field is a point of the play field.
+    pub fn index_of(&self, field: Field) -> Option<usize> {
+        self.points.iter().position(|&point| point == field)
+    }
+
+    /// Returns true if the two points are directly connected by a line.
+    pub fn are_neighbors(&self, index: usize, other_index: usize) -> bool {
+        self.neighbors[index].contains(&other_index)
+    }
+
+    /// Returns all mills the point with the specified index is part of.
+    pub fn mills_containing(&self, index: usize) -> impl Iterator<Item = &'static [usize; 3]> {
+        self.mills.iter().filter(move |mill| mill.contains(&index))
+    }
+
+    /// Returns the indices of all points in the specified row, ordered from left to right.
+    pub fn points_in_row(&self, row: u8) -> impl Iterator<Item = usize> {
+        self.points.iter().enumerate().filter(move |(_, point)| point.1 == row).map(|(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARDS: [&Board; 4] = [&NINE_MENS_MORRIS, &TWELVE_MENS_MORRIS, &THREE_MENS_MORRIS, &SIX_MENS_MORRIS];
+
+    #[test]
+    fn neighbors_are_symmetric() {
+        for board in BOARDS {
+            assert_eq!(board.neighbors.len(), board.point_count());
+
+            for (index, neighbors) in board.neighbors.iter().enumerate() {
+                for &neighbor in neighbors.iter() {
+                    assert!(
+                        board.are_neighbors(neighbor, index),
+                        "{}: {:?} is missing {:?}",
+                        board.name,
+                        board.points[neighbor],
+                        board.points[index]
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mills_are_connected_lines() {
+        for board in BOARDS {
+            for mill in board.mills {
+                assert!(board.are_neighbors(mill[0], mill[1]) && board.are_neighbors(mill[1], mill[2]));
+            }
+        }
+
+        for index in 0..POINT_COUNT {
+            assert_eq!(NINE_MENS_MORRIS.mills_containing(index).count(), 2);
+        }
+        // The corners of the squares are part of a diagonal mill
+        assert_eq!(TWELVE_MENS_MORRIS.mills_containing(0).count(), 3);
+    }
+
+    #[test]
+    fn templates_fit_the_points() {
+        for board in BOARDS {
+            let placeholders = board.ascii_template.iter().flat_map(|line| line.chars()).filter(|&c| c == '@').count();
+            assert_eq!(placeholders, board.point_count());
+
+            // The points are placed row by row from the top, each of them labeled
+            let rows = board.ascii_template.iter().step_by(2).map(|line| line.matches('@').count());
+            let mut row = board.points.iter().map(|point| point.1).max().unwrap();
+            for points_in_line in rows {
+                assert_eq!(points_in_line, board.points_in_row(row).count(), "{}: row {row}", board.name);
+                row -= 1;
+            }
+
+            for point in board.points {
+                let column = board.column_labels.find(point.0).unwrap();
+                assert!(board.ascii_template.iter().any(|line| line.chars().nth(column) == Some('@')));
+            }
+        }
+    }
+}
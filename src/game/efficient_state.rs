@@ -1,12 +1,17 @@
+#[cfg(test)]
 use std::{
     fs::File,
     io::{BufRead, BufReader, BufWriter, Write},
 };
 
+#[cfg(test)]
 use fnv::FnvHashMap;
 use smallvec::SmallVec;
 
-use mill_playfield::{EfficientPlayField,FieldPos,MoveDirection,DirectionToCheck, PlayerColor};
+use mill_playfield::{DirectionToCheck, EfficientPlayField, FieldPos, FieldState, MoveDirection, PlayerColor};
+
+#[cfg(test)]
+use crate::game::position::{notation::PositionParseError, Position};
 
 /// Calculates the possible moves of color, the amount of moves wich lead to a mill for color
 /// and the amount of stones of the other players color, which can be beaten
@@ -27,10 +32,7 @@ pub fn get_move_triple(pf: &mut EfficientPlayField, color: PlayerColor) -> (u32,
         stones_to_take_counter = not_color_positions.len() as u32;
 
         for position in not_color_positions {
-            if 0 < pf.get_mill_count(
-                position,
-                DirectionToCheck::OnAndAcrossRings { player_color: (!color).into() },
-            ) {
+            if 0 < pf.get_mill_count(position, DirectionToCheck::OnAndAcrossRings { player_color: (!color).into() }) {
                 stones_to_take_counter -= 1;
             }
         }
@@ -69,6 +71,56 @@ pub fn get_move_triple(pf: &mut EfficientPlayField, color: PlayerColor) -> (u32,
     (moves_possible_counter, moves_into_mill_counter, stones_to_take_counter)
 }
 
+/// The set phase counterpart of [get_move_triple], in which a stone of the hand can be placed on any empty field.
+/// Calculates the possible placements of color, the amount of placements wich lead to a mill for color
+/// and the amount of stones of the other players color, which can be beaten
+///
+/// Handled extreme cases:
+/// - No stones are left in the hand of color, so the stones are moved & the move triple is returned
+/// - All stones of the opponent are in a mill, so all of them can be beaten
+pub fn get_set_triple(pf: &mut EfficientPlayField, color: PlayerColor, stones_in_hand: u32) -> (u32, u32, u32) {
+    if stones_in_hand == 0 {
+        return get_move_triple(pf, color);
+    }
+
+    let mut placements_possible_counter: u32 = 0;
+    let mut placements_into_mill_counter: u32 = 0;
+    let mut stones_to_take_counter: u32 = 0;
+
+    let (_, not_color_positions) = get_positions(pf, color);
+
+    for ring_index in 0..3 {
+        for field_index in 0..8 {
+            let current_field = FieldPos { ring_index, index: field_index };
+
+            if pf.get_field_state_at(current_field) == 0 {
+                placements_possible_counter += 1;
+
+                let field_state = match color {
+                    PlayerColor::White => FieldState::White,
+                    PlayerColor::Black => FieldState::Black,
+                };
+                pf.set_field_state(current_field, field_state);
+                placements_into_mill_counter +=
+                    pf.get_mill_count(current_field, DirectionToCheck::OnAndAcrossRings { player_color: color.into() });
+                pf.set_field_state(current_field, FieldState::Free);
+            }
+        }
+    }
+
+    for &position in &not_color_positions {
+        if pf.get_mill_count(position, DirectionToCheck::OnAndAcrossRings { player_color: (!color).into() }) == 0 {
+            stones_to_take_counter += 1;
+        }
+    }
+    if stones_to_take_counter == 0 {
+        // All stones of the opposite color are in a mill:
+        stones_to_take_counter = not_color_positions.len() as u32;
+    }
+
+    (placements_possible_counter, placements_into_mill_counter, stones_to_take_counter)
+}
+
 fn calculate_move_tupel(
     pf: &mut EfficientPlayField,
     color: PlayerColor,
@@ -79,7 +131,7 @@ fn calculate_move_tupel(
 ) {
     for ring_index in 0..3 {
         for field_index in 0..8 {
-            let current_field = FieldPos {ring_index, index: field_index};
+            let current_field = FieldPos { ring_index, index: field_index };
             // Current field state sifted to the LSB
             let current_field_state = pf.get_field_state_at(current_field);
 
@@ -114,10 +166,9 @@ fn calculate_move_tupel(
             else {
                 *overall_stones_of_opposite_color_counter += 1;
 
-                if pf.get_mill_count(
-                    current_field,
-                    DirectionToCheck::OnAndAcrossRings { player_color: (!color).into() },
-                ) == 0
+                if pf
+                    .get_mill_count(current_field, DirectionToCheck::OnAndAcrossRings { player_color: (!color).into() })
+                    == 0
                 {
                     *stones_to_take_counter += 1;
                 }
@@ -134,7 +185,6 @@ fn calculate_tupels_across_rings(pf: &mut EfficientPlayField, field: FieldPos, c
 
     let (next_rings_field_state, previous_rings_field_state) = pf.get_neigbor_rings_field_states(field);
 
-
     match field.ring_index {
         // Inner Ring
         0 if next_rings_field_state == 0 => {
@@ -190,13 +240,13 @@ fn get_positions(pf: &EfficientPlayField, color: PlayerColor) -> (SmallVec<[Fiel
 
     for ring_index in 0..3 {
         for field_index in 0..8 {
-            let current_field = FieldPos{ring_index, index: field_index};
+            let current_field = FieldPos { ring_index, index: field_index };
             let state = pf.get_field_state_at(current_field);
 
             if state == (<PlayerColor as Into<u16>>::into(color) << (field_index * 2)) {
                 color_positions.push(current_field)
             } else if state == (<PlayerColor as Into<u16>>::into(!color) << (field_index * 2)) {
-                not_color_positions.push(current_field )
+                not_color_positions.push(current_field)
             }
         }
     }
@@ -204,7 +254,8 @@ fn get_positions(pf: &EfficientPlayField, color: PlayerColor) -> (SmallVec<[Fiel
     (color_positions, not_color_positions)
 }
 
-fn process_input_fields_canonical() {
+#[cfg(test)]
+fn process_input_fields_canonical() -> Result<(), PositionParseError> {
     let (reader, mut writer) = init_writer_reader("input_felder_4.txt");
     let mut output_map = FnvHashMap::<EfficientPlayField, usize>::default();
 
@@ -212,7 +263,7 @@ fn process_input_fields_canonical() {
         // Idk why but the reference output.txt starts counting on 1...
         let line_index = line_index + 1;
 
-        let mut playfield = EfficientPlayField::from_coded(&line_content.unwrap());
+        let (mut playfield, _, _) = parse_input_line(&line_content.unwrap())?;
         println!("{playfield}");
         let canonical_form = playfield.get_canon_form();
         println!("{canonical_form}");
@@ -225,16 +276,18 @@ fn process_input_fields_canonical() {
             }
         }
     }
+    Ok(())
 }
 
-fn process_input_fields_tuple() {
+#[cfg(test)]
+fn process_input_fields_tuple() -> Result<(), PositionParseError> {
     let (reader, mut writer) = init_writer_reader("input_felder_5.txt");
 
     for (line_index, line_content) in reader.lines().enumerate() {
         let line_content = line_content.unwrap();
-        let mut playfield = EfficientPlayField::from_coded(&line_content);
+        let (mut playfield, side_to_move, stones_in_hand) = parse_input_line(&line_content)?;
 
-        let (x, y, z) = get_move_triple(&mut playfield, PlayerColor::White);
+        let (x, y, z) = get_set_triple(&mut playfield, side_to_move, stones_in_hand);
 
         assert!({
             println!("Input {line_index}: {line_content}\n{playfield}");
@@ -244,9 +297,39 @@ fn process_input_fields_tuple() {
 
         writeln!(writer, "{x} {y} {z}").unwrap();
     }
+    Ok(())
+}
+
+/// Parses a line of the input files, which either is the plain 24 character board with white to move or a whole
+/// [Position] in the notation of [crate::game::position::notation].
+/// Returns the stones in the hand of the side to move as well, which plain boards don't have.
+///
+/// Handled extreme cases:
+/// - The plain board doesn't consist of 24 fields or contains other characters than `B`, `W` & `E`
+/// - The position is invalid, which is reported by [Position::from_notation]
+#[cfg(test)]
+fn parse_input_line(line: &str) -> Result<(EfficientPlayField, PlayerColor, u32), PositionParseError> {
+    let board = line.trim();
+    if !board.contains(char::is_whitespace) {
+        let error = |column, message| PositionParseError { input: line.to_string(), column, message };
+
+        if let Some(index) = board.chars().position(|state| !matches!(state, 'B' | 'W' | 'E')) {
+            return Err(error(index + 1, "The fields of the board must be either B, W or E."));
+        }
+        if board.chars().count() != 24 {
+            return Err(error(1, "The board must consist of exactly 24 fields."));
+        }
+        return Ok((EfficientPlayField::from_coded(board), PlayerColor::White, 0));
+    }
+
+    let position = line.parse::<Position>()?;
+    // The position is parsed with the default rules, so it's always on the board of 24 points
+    let playfield = EfficientPlayField::try_from(&position.play_field).unwrap();
+    Ok((playfield, position.side_to_move, position.get_stones_in_hand(position.side_to_move)))
 }
 
 /// Inits the reader and writer on the default files `input_felder.txt` and `output.txt`
+#[cfg(test)]
 fn init_writer_reader(input: &str) -> (BufReader<File>, BufWriter<File>) {
     let input_felder_txt =
         File::open(input).expect("The 'input_felder.txt' file was not found in the projects root...");
@@ -262,18 +345,30 @@ fn init_writer_reader(input: &str) -> (BufReader<File>, BufWriter<File>) {
 mod tests {
     use mill_playfield::PlayerColor;
 
-    use crate::game::efficient_state::get_move_triple;
+    use crate::game::efficient_state::{get_move_triple, get_set_triple};
 
     use super::EfficientPlayField;
 
     #[test]
     fn assignment4() {
-        super::process_input_fields_canonical();
+        super::process_input_fields_canonical().unwrap();
     }
 
     #[test]
     fn assignment5() {
-        super::process_input_fields_tuple();
+        super::process_input_fields_tuple().unwrap();
+    }
+
+    #[test]
+    fn input_lines_are_validated() {
+        assert_eq!(super::parse_input_line("EWWBBEEEEEWBBEEEEEEEBEXB").unwrap_err().column, 23);
+        assert!(super::parse_input_line("EWWBBEEEEEWBBEEEEEEEBEE").is_err());
+        assert!(super::parse_input_line("WWEEEEEEBBEEEEEEEEEEEEEE w s 7 7").is_err());
+
+        let (_, side_to_move, stones_in_hand) =
+            super::parse_input_line("WWEEEEEEBBEEEEEEEEEEEEEE b s 7 7 0 5").unwrap();
+        assert!(matches!(side_to_move, PlayerColor::Black));
+        assert_eq!(stones_in_hand, 7);
     }
 
     #[test]
@@ -285,4 +380,14 @@ mod tests {
         let (x, y, z) = get_move_triple(&mut test_epf, PlayerColor::White);
         println!("{x} {y} {z}")
     }
+
+    #[test]
+    fn set_triple_counts_the_placements() {
+        let mut test_epf = EfficientPlayField::from_coded("WWEEEEEEBEEEEEEEEEEEEEEE");
+
+        // Every empty field can be placed on, but only C5 closes a mill
+        assert_eq!(get_set_triple(&mut test_epf, PlayerColor::White, 7), (21, 1, 1));
+        let move_triple = get_move_triple(&mut test_epf, PlayerColor::White);
+        assert_eq!(get_set_triple(&mut test_epf, PlayerColor::White, 0), move_triple);
+    }
 }
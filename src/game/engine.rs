@@ -0,0 +1,512 @@
+//! Contains the [EnginePosition], which is the bit packed counterpart of the [Position] the search engine works on,
+//! and the [EngineMove]s generated on it.
+//!
+//! Only the Nine Men's Morris board is supported, as the [EfficientPlayField] always consists of 3 rings of 8 fields
+//! without diagonals. The points of the board are indexed from 0 to 23, ring by ring from the inner to the outer one,
+//! so the point `ring_index * 8 + index` is the [FieldPos] `{ ring_index, index }`.
+//!
+//! Every [EnginePosition] carries its [zobrist] key, which is a 64 bit key updated on every change of the position,
+//! along with the keys of its mirrored & rotated variants.
+
+use std::hash::{Hash, Hasher};
+
+use mill_playfield::{DirectionToCheck, EfficientPlayField, FieldPos, FieldState, PlayerColor};
+use smallvec::SmallVec;
+
+use crate::game::moves::{Move, Takes};
+use crate::game::position::Position;
+use crate::game::rules::{RuleSet, TakingFromMills};
+use crate::game::state::conversion::field_pos_to_field;
+use crate::game::state::{PlayField, PlayFieldError};
+use transposition::Symmetry;
+use zobrist::ZOBRIST_KEYS;
+
+pub mod evaluation;
+pub mod mcts;
+pub mod search;
+pub mod transposition;
+pub mod zobrist;
+
+pub const POINT_COUNT: u8 = 24;
+
+/// A move on the [EnginePosition], which mirrors a [Move] with the points of the board instead of the [Field]s
+///
+/// [Field]: crate::game::Field
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct EngineMove {
+    /// The point the stone is moved from, which doesn't exist for placements
+    pub start: Option<u8>,
+    pub target: u8,
+    pub jump: bool,
+    /// The points of the opponents stones taken, filled up from the first one
+    pub takes: [Option<u8>; 2],
+}
+
+impl EngineMove {
+    pub fn takes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.takes.iter().flatten().copied()
+    }
+}
+
+impl From<EngineMove> for Move {
+    fn from(r#move: EngineMove) -> Self {
+        let field = |point| field_pos_to_field(to_field_pos(point));
+        let takes = r#move.takes().map(field).collect::<Takes>();
+
+        match r#move.start {
+            None => Move::Place { field: field(r#move.target), takes },
+            Some(start) if r#move.jump => Move::Jump {
+                start_field: field(start),
+                target_field: field(r#move.target),
+                takes,
+            },
+            Some(start) => Move::Slide {
+                start_field: field(start),
+                target_field: field(r#move.target),
+                takes,
+            },
+        }
+    }
+}
+
+/// The outcome of a position in which the game is over, from the view of the side to move
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Loss,
+    Draw,
+}
+
+/// The stones on the board, the side to move & the stones left in the hands.
+/// The draw conditions of the rules depending on the history of the game aren't known to it.
+///
+/// The fields are only changed by the position itself, which keeps its Zobrist key up to date with them.
+#[derive(Clone, Copy, Debug)]
+pub struct EnginePosition {
+    play_field: EfficientPlayField,
+    side_to_move: PlayerColor,
+    // first one: white, second one: black
+    stones_in_hand: (u32, u32),
+    rules: RuleSet,
+    // The Zobrist keys of the position & its variants, ordered like Symmetry::all, so the first one is its own key
+    symmetric_keys: [u64; Symmetry::COUNT],
+}
+
+/// Two [EnginePosition]s are equal if the stones on the board & in the hands and the side to move are
+impl PartialEq for EnginePosition {
+    fn eq(&self, other: &Self) -> bool {
+        self.play_field == other.play_field
+            && is_same(self.side_to_move, other.side_to_move)
+            && self.stones_in_hand == other.stones_in_hand
+    }
+}
+
+impl Eq for EnginePosition {}
+
+/// Only the Zobrist key is hashed, which already depends on everything compared by [PartialEq]
+impl Hash for EnginePosition {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get_zobrist_key().hash(state);
+    }
+}
+
+/// Handled extreme cases:
+/// - The rules are played on another board than the one of Nine Men's Morris, which the conversion of the play field
+///   to the [EfficientPlayField] rejects
+/// - The players have more than [zobrist::MAX_STONES_IN_HAND] stones
+impl TryFrom<&Position> for EnginePosition {
+    type Error = PlayFieldError;
+
+    fn try_from(position: &Position) -> Result<Self, Self::Error> {
+        let rules = *position.get_rules();
+        check_stone_count(&rules)?;
+
+        let mut engine_position = EnginePosition {
+            play_field: EfficientPlayField::try_from(&position.play_field)?,
+            side_to_move: position.side_to_move,
+            stones_in_hand: position.stones_in_hand,
+            rules,
+            symmetric_keys: [0; Symmetry::COUNT],
+        };
+        engine_position.symmetric_keys = zobrist::compute_symmetric_keys(&engine_position);
+        Ok(engine_position)
+    }
+}
+
+impl EnginePosition {
+    /// Returns the starting position with white to move, on which the specified rules apply.
+    ///
+    /// Handled extreme cases:
+    /// - The rules are played on another board than the one of Nine Men's Morris, which is checked just like the
+    ///   conversion of a [Position] does
+    /// - The players have more than [zobrist::MAX_STONES_IN_HAND] stones
+    pub fn new(rules: RuleSet) -> Result<Self, PlayFieldError> {
+        check_stone_count(&rules)?;
+
+        let mut position = EnginePosition {
+            play_field: EfficientPlayField::try_from(&PlayField::with_rules(rules))?,
+            side_to_move: PlayerColor::White,
+            stones_in_hand: (rules.stones_per_player, rules.stones_per_player),
+            rules,
+            symmetric_keys: [0; Symmetry::COUNT],
+        };
+        position.symmetric_keys = zobrist::compute_symmetric_keys(&position);
+        Ok(position)
+    }
+
+    pub fn get_play_field(&self) -> &EfficientPlayField {
+        &self.play_field
+    }
+
+    pub fn get_side_to_move(&self) -> PlayerColor {
+        self.side_to_move
+    }
+
+    /// Returns the Zobrist key of the position, see [zobrist]
+    pub fn get_zobrist_key(&self) -> u64 {
+        self.symmetric_keys[0]
+    }
+
+    pub fn get_rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    pub fn get_stones_in_hand(&self, color: PlayerColor) -> u32 {
+        match color {
+            PlayerColor::White => self.stones_in_hand.0,
+            PlayerColor::Black => self.stones_in_hand.1,
+        }
+    }
+
+    /// Returns the color of the stone on the point, if there is one
+    pub fn get_stone(&self, point: u8) -> Option<PlayerColor> {
+        let field_pos = to_field_pos(point);
+        // The state of the field shifted to the LSB
+        let field_state = self.play_field.get_field_state_at(field_pos) >> (field_pos.index * 2);
+
+        match field_state {
+            0 => None,
+            state if state == PlayerColor::White.into() => Some(PlayerColor::White),
+            _ => Some(PlayerColor::Black),
+        }
+    }
+
+    pub fn get_points_of(&self, color: PlayerColor) -> impl Iterator<Item = u8> + '_ {
+        (0..POINT_COUNT).filter(move |&point| matches!(self.get_stone(point), Some(stone) if is_same(stone, color)))
+    }
+
+    pub fn get_free_points(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..POINT_COUNT).filter(|&point| self.get_stone(point).is_none())
+    }
+
+    pub fn get_amount_of_stones(&self, color: PlayerColor) -> u32 {
+        self.get_points_of(color).count() as u32
+    }
+
+    /// Returns if the stone on the point is part of a mill of the specified color
+    pub fn is_in_mill(&self, point: u8, color: PlayerColor) -> bool {
+        let direction = DirectionToCheck::OnAndAcrossRings { player_color: color.into() };
+        0 < self.play_field.get_mill_count(to_field_pos(point), direction)
+    }
+
+    /// Returns the points the stone on the point might slide to, which are the free neighbors along the lines
+    pub fn get_free_neighbors(&self, point: u8) -> SmallVec<[u8; 4]> {
+        let (ring_index, index) = (point / 8, point % 8);
+        let mut neighbors = SmallVec::<[u8; 4]>::new();
+
+        neighbors.push(ring_index * 8 + (index + 7) % 8);
+        neighbors.push(ring_index * 8 + (index + 1) % 8);
+        // The middle fields are connected across the rings
+        if index % 2 == 0 {
+            if 0 < ring_index {
+                neighbors.push(point - 8);
+            }
+            if ring_index < 2 {
+                neighbors.push(point + 8);
+            }
+        }
+
+        neighbors.retain(|neighbor| self.get_stone(*neighbor).is_none());
+        neighbors
+    }
+
+    /// Returns the points of the opponents stones the player with the specified color is permitted to take
+    pub fn get_takeable_points(&self, color: PlayerColor) -> SmallVec<[u8; 12]> {
+        let may_take_from_mills = match self.rules.taking_from_mills {
+            TakingFromMills::Never => false,
+            TakingFromMills::IfAllInMills => self.get_points_of(!color).all(|point| self.is_in_mill(point, !color)),
+            TakingFromMills::Always => true,
+        };
+
+        self.get_points_of(!color).filter(|&point| may_take_from_mills || !self.is_in_mill(point, !color)).collect()
+    }
+
+    /// Returns the outcome for the side to move, if the game is over: A loss if the player has too few stones left
+    /// or can't move although the rules count it as a loss, a draw if he can't move otherwise.
+    /// The moves of the position must be passed, so they aren't generated twice.
+    pub fn get_outcome(&self, moves: &[EngineMove]) -> Option<Outcome> {
+        let color = self.side_to_move;
+        let stones_in_hand = self.get_stones_in_hand(color);
+
+        if self.get_amount_of_stones(color) + stones_in_hand <= self.rules.losing_stone_count {
+            return Some(Outcome::Loss);
+        }
+        if !moves.is_empty() {
+            return None;
+        }
+
+        let board_is_full = self.get_free_points().next().is_none();
+        match self.rules.blocked_loses && stones_in_hand == 0 {
+            true if board_is_full && self.rules.draw_conditions.full_board => Some(Outcome::Draw),
+            true => Some(Outcome::Loss),
+            false => Some(Outcome::Draw),
+        }
+    }
+
+    /// Generates every legal [EngineMove] of the side to move, following the rules of
+    /// [crate::game::state::PlayField::generate_moves]
+    pub fn generate_moves(&self) -> Vec<EngineMove> {
+        let color = self.side_to_move;
+        let stones_in_hand = self.get_stones_in_hand(color);
+        let mut moves = Vec::new();
+
+        if 0 < stones_in_hand {
+            for target in self.get_free_points() {
+                let r#move = EngineMove { start: None, target, jump: false, takes: [None; 2] };
+                self.push_with_takes(r#move, &mut moves);
+            }
+
+            if !self.rules.moving_in_set_phase {
+                return moves;
+            }
+        }
+
+        let can_jump = stones_in_hand == 0 && self.rules.can_fly(self.get_amount_of_stones(color));
+
+        for start in self.get_points_of(color) {
+            let targets: SmallVec<[u8; 24]> = match can_jump {
+                true => self.get_free_points().collect(),
+                false => self.get_free_neighbors(start).into_iter().collect(),
+            };
+
+            for target in targets {
+                let r#move = EngineMove { start: Some(start), target, jump: can_jump, takes: [None; 2] };
+                self.push_with_takes(r#move, &mut moves);
+            }
+        }
+
+        moves
+    }
+
+    /// Returns the position after the move, which must be legal
+    pub fn make_move(&self, r#move: &EngineMove) -> Self {
+        let color = self.side_to_move;
+        let mut position = *self;
+
+        if let Some(start) = r#move.start {
+            position.set_stone(start, None);
+        } else {
+            position.set_stones_in_hand(color, self.get_stones_in_hand(color) - 1);
+        }
+        position.set_stone(r#move.target, Some(color));
+        for take in r#move.takes() {
+            position.set_stone(take, None);
+        }
+
+        position.set_side_to_move(!color);
+        position
+    }
+
+    // The following setters XOR the Zobrist keys of the state replaced & of the new one into the keys of the position
+
+    fn set_stone(&mut self, point: u8, stone: Option<PlayerColor>) {
+        if let Some(replaced_stone) = self.get_stone(point) {
+            self.xor_symmetric_stones(point, replaced_stone);
+        }
+        if let Some(stone) = stone {
+            self.xor_symmetric_stones(point, stone);
+        }
+
+        let field_state = match stone {
+            None => FieldState::Free,
+            Some(PlayerColor::White) => FieldState::White,
+            Some(PlayerColor::Black) => FieldState::Black,
+        };
+        self.play_field.set_field_state(to_field_pos(point), field_state);
+    }
+
+    fn set_stones_in_hand(&mut self, color: PlayerColor, amount: u32) {
+        // The hands aren't affected by the symmetries, so their keys are the same for every variant
+        self.xor_into_all_keys(
+            ZOBRIST_KEYS.stones_in_hand(color, self.get_stones_in_hand(color))
+                ^ ZOBRIST_KEYS.stones_in_hand(color, amount),
+        );
+
+        match color {
+            PlayerColor::White => self.stones_in_hand.0 = amount,
+            PlayerColor::Black => self.stones_in_hand.1 = amount,
+        }
+    }
+
+    fn set_side_to_move(&mut self, color: PlayerColor) {
+        self.xor_into_all_keys(ZOBRIST_KEYS.side_to_move(self.side_to_move) ^ ZOBRIST_KEYS.side_to_move(color));
+        self.side_to_move = color;
+    }
+
+    fn xor_symmetric_stones(&mut self, point: u8, stone: PlayerColor) {
+        let stone_keys = ZOBRIST_KEYS.symmetric_stones(point, stone);
+        for (key, stone_key) in self.symmetric_keys.iter_mut().zip(stone_keys) {
+            *key ^= stone_key;
+        }
+    }
+
+    fn xor_into_all_keys(&mut self, key: u64) {
+        self.symmetric_keys.iter_mut().for_each(|symmetric_key| *symmetric_key ^= key);
+    }
+
+    /// Pushes the move once for every combination of stones which can be taken after it, like
+    /// [crate::game::state::PlayField::generate_moves] does
+    fn push_with_takes(&self, r#move: EngineMove, moves: &mut Vec<EngineMove>) {
+        let color = self.side_to_move;
+        let mut simulation = *self;
+        if let Some(start) = r#move.start {
+            simulation.set_stone(start, None);
+        }
+        simulation.set_stone(r#move.target, Some(color));
+
+        let direction = DirectionToCheck::OnAndAcrossRings { player_color: color.into() };
+        let mills_closed = simulation.play_field.get_mill_count(to_field_pos(r#move.target), direction);
+        let amount_of_takes = self.rules.takes_for_mills(mills_closed as usize);
+        let mut take_combinations = vec![(simulation, r#move)];
+
+        for take_index in 0..amount_of_takes {
+            let mut next_take_combinations: Vec<(EnginePosition, EngineMove)> = Vec::new();
+
+            for (position, r#move) in take_combinations.iter() {
+                for take in position.get_takeable_points(color) {
+                    let mut r#move = *r#move;
+                    r#move.takes[take_index] = Some(take);
+
+                    // Taking the same stones in another order leads to the same position
+                    let is_duplicate = next_take_combinations.iter().any(|(_, other_move)| {
+                        r#move.takes().all(|take| other_move.takes().any(|other_take| other_take == take))
+                    });
+                    if is_duplicate {
+                        continue;
+                    }
+
+                    let mut simulation = *position;
+                    simulation.set_stone(take, None);
+                    next_take_combinations.push((simulation, r#move));
+                }
+            }
+
+            // The opponent has no stones left which could be taken
+            if next_take_combinations.is_empty() {
+                break;
+            }
+            take_combinations = next_take_combinations;
+        }
+
+        moves.extend(take_combinations.into_iter().map(|(_, r#move)| r#move));
+    }
+}
+
+pub fn to_field_pos(point: u8) -> FieldPos {
+    FieldPos {
+        ring_index: (point / 8) as usize,
+        index: (point % 8) as usize,
+    }
+}
+
+/// Rejects rules with more stones per player than the [zobrist] keys exist for
+fn check_stone_count(rules: &RuleSet) -> Result<(), PlayFieldError> {
+    match rules.stones_per_player as usize <= zobrist::MAX_STONES_IN_HAND {
+        true => Ok(()),
+        false => Err(PlayFieldError::UnsupportedStoneCountError {
+            stones_per_player: rules.stones_per_player,
+            max_stones: zobrist::MAX_STONES_IN_HAND,
+        }),
+    }
+}
+
+fn is_same(color: PlayerColor, other_color: PlayerColor) -> bool {
+    matches!((color, other_color), (PlayerColor::White, PlayerColor::White) | (PlayerColor::Black, PlayerColor::Black))
+}
+
+/// The positions & the helper shared by the tests of the engine
+#[cfg(test)]
+pub mod testing {
+    use super::EnginePosition;
+    use crate::game::position::Position;
+
+    /// White closes a mill on C5 in the set phase, taking one of the two black stones
+    pub const MILL_IN_SET_PHASE: &str = "WWEEEEEEBBEEEEEEEEEEEEEE w s 7 7 0 5";
+    /// White jumps into a mill on C5, taking one of the last three black stones
+    pub const WINNING_JUMP: &str = "WWEEEEEEEEEEEEEEBBBEEWEE w m 0 0 0 30";
+
+    /// Parses the position in the notation of [Position] & converts it, panicking if it isn't supported
+    pub fn engine_position(notation: &str) -> EnginePosition {
+        EnginePosition::try_from(&notation.parse::<Position>().unwrap()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::MILL_IN_SET_PHASE;
+    use super::{EngineMove, EnginePosition};
+    use crate::game::moves::Move;
+    use crate::game::position::Position;
+    use crate::game::rules::RuleSet;
+    use crate::game::state::PlayFieldError;
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        let too_many_stones = RuleSet { stones_per_player: 13, ..Default::default() };
+        assert!(matches!(
+            EnginePosition::new(too_many_stones),
+            Err(PlayFieldError::UnsupportedStoneCountError { stones_per_player: 13, max_stones: 12 })
+        ));
+        assert!(matches!(
+            EnginePosition::try_from(&Position::new(too_many_stones)),
+            Err(PlayFieldError::UnsupportedStoneCountError { .. })
+        ));
+        assert!(matches!(
+            EnginePosition::try_from(&Position::new(RuleSet::twelve_mens_morris())),
+            Err(PlayFieldError::UnsupportedBoardError { .. })
+        ));
+        assert!(matches!(
+            EnginePosition::new(RuleSet::twelve_mens_morris()),
+            Err(PlayFieldError::UnsupportedBoardError { .. })
+        ));
+    }
+
+    #[test]
+    fn generates_the_moves_of_the_play_field() {
+        let mut position = Position::default();
+        for notation in ["a1", "g7", "d1", "g4", "a4", "b2", "g1xg4"] {
+            position.apply_move(&notation.parse().unwrap()).unwrap();
+        }
+        let engine_position = EnginePosition::try_from(&position).unwrap();
+
+        let mut moves = engine_position.generate_moves().into_iter().map(Move::from).collect::<Vec<_>>();
+        let mut expected_moves = position.generate_moves();
+        moves.sort_by_key(Move::to_string);
+        expected_moves.sort_by_key(Move::to_string);
+        assert_eq!(moves, expected_moves);
+    }
+
+    #[test]
+    fn takes_stones_on_mills() {
+        let position: Position = MILL_IN_SET_PHASE.parse().unwrap();
+        let engine_position = EnginePosition::try_from(&position).unwrap();
+
+        let mills = engine_position.generate_moves().into_iter().filter(|r#move| r#move.takes[0].is_some());
+        let mill: EngineMove = mills.clone().next().unwrap();
+        assert_eq!(mills.count(), 2);
+
+        let next_position = engine_position.make_move(&mill);
+        assert_eq!(next_position.get_amount_of_stones(position.side_to_move), 3);
+        assert_eq!(next_position.get_amount_of_stones(!position.side_to_move), 1);
+        assert_eq!(next_position.stones_in_hand, (6, 7));
+    }
+}
@@ -0,0 +1,408 @@
+//! Contains the static evaluation of [EnginePosition]s, which rates a position by [Term]s counted for both players,
+//! e.g. the stones, the moves possible & the mills about to be closed. The terms of the opponent are subtracted from
+//! the ones of the side to move & multiplied by the [Weights] of the current [Phase].
+//!
+//! The weights are read from a config file in the format of [Weights::from_str], in which every line holds the name
+//! of a term followed by its weight in the set, the move & the jump phase, e.g. `mobility 1 3 0`.
+//! Empty lines & lines starting with `#` are skipped, terms not listed keep their default weights.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use mill_playfield::PlayerColor;
+use smallvec::SmallVec;
+
+use super::{is_same, EnginePosition};
+use crate::game::efficient_state::get_set_triple;
+
+pub const TERM_COUNT: usize = 8;
+pub const PHASE_COUNT: usize = 3;
+
+/// The points of the mills of the board, ring by ring & afterwards the ones across the rings
+#[rustfmt::skip]
+pub const MILLS: [[u8; 3]; 16] = [
+    [1, 2, 3], [3, 4, 5], [5, 6, 7], [7, 0, 1],
+    [9, 10, 11], [11, 12, 13], [13, 14, 15], [15, 8, 9],
+    [17, 18, 19], [19, 20, 21], [21, 22, 23], [23, 16, 17],
+    [0, 8, 16], [2, 10, 18], [4, 12, 20], [6, 14, 22],
+];
+
+/// A property of the position counted for each player
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Term {
+    /// The stones on the board
+    Stones,
+    StonesInHand,
+    /// The placements, slides or jumps possible, counted by [get_set_triple]
+    Mobility,
+    /// The placements, slides or jumps closing a mill, counted by [get_set_triple]
+    MovesIntoMill,
+    /// The stones of the opponent which could be taken, counted by [get_set_triple]
+    TakeableStones,
+    /// The stones which can't slide, as all of their neighbors are occupied
+    BlockedStones,
+    /// The mills with two stones of the player, whose third point is free
+    OpenMills,
+    /// The stones of closed mills which can slide into another mill & back again, which is called "Zwickmühle"
+    DoubleMills,
+}
+
+impl Term {
+    pub const ALL: [Term; TERM_COUNT] = [
+        Term::Stones,
+        Term::StonesInHand,
+        Term::Mobility,
+        Term::MovesIntoMill,
+        Term::TakeableStones,
+        Term::BlockedStones,
+        Term::OpenMills,
+        Term::DoubleMills,
+    ];
+
+    /// The name of the term in the config file
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Term::Stones => "stones",
+            Term::StonesInHand => "stones_in_hand",
+            Term::Mobility => "mobility",
+            Term::MovesIntoMill => "moves_into_mill",
+            Term::TakeableStones => "takeable_stones",
+            Term::BlockedStones => "blocked_stones",
+            Term::OpenMills => "open_mills",
+            Term::DoubleMills => "double_mills",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Term::ALL.iter().position(|term| term == self).unwrap()
+    }
+}
+
+/// The phase of the position, which the weights depend on
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Phase {
+    /// At least one of the players has stones left in his hand
+    Set,
+    Move,
+    /// At least one of the players may jump, as he only has a few stones left
+    Jump,
+}
+
+impl Phase {
+    pub fn of(position: &EnginePosition) -> Self {
+        let colors = [PlayerColor::White, PlayerColor::Black];
+        let rules = position.get_rules();
+
+        if colors.iter().any(|&color| 0 < position.get_stones_in_hand(color)) {
+            Phase::Set
+        } else if colors.iter().any(|&color| rules.can_fly(position.get_amount_of_stones(color))) {
+            Phase::Jump
+        } else {
+            Phase::Move
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Phase::Set => 0,
+            Phase::Move => 1,
+            Phase::Jump => 2,
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Set => "set phase",
+            Phase::Move => "move phase",
+            Phase::Jump => "jump phase",
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum WeightsError {
+    FileError { message: String },
+    ParseError { line: usize, message: &'static str },
+}
+
+impl Display for WeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightsError::FileError { message } => f.write_fmt(format_args!("Error reading the weights - {message}")),
+            WeightsError::ParseError { line, message } => {
+                f.write_fmt(format_args!("Error parsing line {line} of the weights - {message}"))
+            }
+        }
+    }
+}
+
+/// The weights of the [Term]s in every [Phase]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Weights {
+    // Indexed by [phase][term]
+    weights: [[i32; TERM_COUNT]; PHASE_COUNT],
+}
+
+/// Stones are worth the most, the other terms decide between positions with the same stones.
+/// Mobility doesn't matter for jumping stones, while mills which can be closed again & again matter most in the move
+/// phase.
+impl Default for Weights {
+    fn default() -> Self {
+        let mut weights = Weights { weights: [[0; TERM_COUNT]; PHASE_COUNT] };
+        let defaults = [
+            (Term::Stones, [100, 100, 100]),
+            (Term::StonesInHand, [100, 100, 100]),
+            (Term::Mobility, [1, 3, 0]),
+            (Term::MovesIntoMill, [5, 15, 25]),
+            (Term::TakeableStones, [2, 2, 2]),
+            (Term::BlockedStones, [-1, -4, 0]),
+            (Term::OpenMills, [10, 5, 10]),
+            (Term::DoubleMills, [20, 40, 20]),
+        ];
+
+        for (term, phase_weights) in defaults {
+            for phase in [Phase::Set, Phase::Move, Phase::Jump] {
+                weights.set_weight(term, phase, phase_weights[phase.index()]);
+            }
+        }
+        weights
+    }
+}
+
+/// Parses the weights in the format described in the module documentation, starting from the default weights.
+///
+/// Handled extreme cases:
+/// - The name of the term is unknown
+/// - There aren't exactly 3 weights following the name
+/// - A weight isn't a number
+impl FromStr for Weights {
+    type Err = WeightsError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut weights = Weights::default();
+
+        for (line_index, line) in input.lines().enumerate() {
+            let error = |message| Err(WeightsError::ParseError { line: line_index + 1, message });
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let name = parts.next().unwrap();
+            let Some(term) = Term::ALL.into_iter().find(|term| term.get_name() == name) else {
+                return error("The name of the term is unknown.");
+            };
+
+            let phase_weights = parts.map(str::parse::<i32>).collect::<Vec<_>>();
+            if phase_weights.len() != PHASE_COUNT {
+                return error("Every term must be followed by the weights of the set, the move & the jump phase.");
+            }
+            for (phase, weight) in [Phase::Set, Phase::Move, Phase::Jump].into_iter().zip(phase_weights) {
+                match weight {
+                    Ok(weight) => weights.set_weight(term, phase, weight),
+                    Err(_) => return error("The weights must be whole numbers."),
+                }
+            }
+        }
+
+        Ok(weights)
+    }
+}
+
+/// Prints the weights in the format of the config file, so the defaults can be used as a template
+impl Display for Weights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("# term, followed by its weights in the set, move & jump phase\n")?;
+        for term in Term::ALL {
+            let [set, r#move, jump] = [Phase::Set, Phase::Move, Phase::Jump].map(|phase| self.get_weight(term, phase));
+            f.write_fmt(format_args!("{:<16}{set:>5}{:>5}{jump:>5}\n", term.get_name(), r#move))?;
+        }
+        Ok(())
+    }
+}
+
+impl Weights {
+    /// Reads the weights from the config file at the path
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, WeightsError> {
+        match fs::read_to_string(path) {
+            Ok(content) => content.parse(),
+            Err(error) => Err(WeightsError::FileError { message: error.to_string() }),
+        }
+    }
+
+    pub fn get_weight(&self, term: Term, phase: Phase) -> i32 {
+        self.weights[phase.index()][term.index()]
+    }
+
+    pub fn set_weight(&mut self, term: Term, phase: Phase, weight: i32) {
+        self.weights[phase.index()][term.index()] = weight;
+    }
+
+    /// Rates the position from the view of the side to move, which is the score of [Weights::explain] without building
+    /// the breakdown
+    pub fn evaluate(&self, position: &EnginePosition) -> i32 {
+        let color = position.get_side_to_move();
+        let weights = &self.weights[Phase::of(position).index()];
+        let (own_terms, opponents_terms) = (count_terms(position, color), count_terms(position, !color));
+
+        (0..TERM_COUNT).map(|index| weights[index] * (own_terms[index] - opponents_terms[index])).sum()
+    }
+
+    /// Returns the terms of both players making up the score of [Weights::evaluate]
+    pub fn explain(&self, position: &EnginePosition) -> Breakdown {
+        let color = position.get_side_to_move();
+        let phase = Phase::of(position);
+        let (own_terms, opponents_terms) = (count_terms(position, color), count_terms(position, !color));
+
+        let terms = Term::ALL
+            .into_iter()
+            .map(|term| TermScore {
+                term,
+                own: own_terms[term.index()],
+                opponent: opponents_terms[term.index()],
+                weight: self.get_weight(term, phase),
+            })
+            .collect();
+        Breakdown { side_to_move: color, phase, terms }
+    }
+}
+
+/// A [Term] counted for both players & its weight in the current phase
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TermScore {
+    pub term: Term,
+    /// The count of the side to move
+    pub own: i32,
+    pub opponent: i32,
+    pub weight: i32,
+}
+
+impl TermScore {
+    pub fn get_score(&self) -> i32 {
+        self.weight * (self.own - self.opponent)
+    }
+}
+
+/// The evaluation of a position term by term
+#[derive(Clone, Debug)]
+pub struct Breakdown {
+    pub side_to_move: PlayerColor,
+    pub phase: Phase,
+    pub terms: Vec<TermScore>,
+}
+
+impl Breakdown {
+    /// Returns the score of the position from the view of the side to move, which is the sum of the term scores
+    pub fn get_score(&self) -> i32 {
+        self.terms.iter().map(TermScore::get_score).sum()
+    }
+}
+
+/// Prints a line for every term, e.g. `stones           7 - 6 =  1 x  100 =  100`, followed by the total score
+impl Display for Breakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for term_score in &self.terms {
+            f.write_fmt(format_args!(
+                "{:<16}{:>2} - {:>2} = {:>3} x {:>4} = {:>5}\n",
+                term_score.term.get_name(),
+                term_score.own,
+                term_score.opponent,
+                term_score.own - term_score.opponent,
+                term_score.weight,
+                term_score.get_score()
+            ))?;
+        }
+        f.write_fmt(format_args!("total {} for {} to move in the {}", self.get_score(), self.side_to_move, self.phase))
+    }
+}
+
+/// Counts the terms of the player with the specified color, indexed like [Term::ALL]
+fn count_terms(position: &EnginePosition, color: PlayerColor) -> [i32; TERM_COUNT] {
+    let mut play_field = *position.get_play_field();
+    let (moves_possible, moves_into_mill, stones_to_take) =
+        get_set_triple(&mut play_field, color, position.get_stones_in_hand(color));
+
+    let is_color = |point: u8| position.get_stone(point).is_some_and(|stone| is_same(stone, color));
+    // Counted for every position evaluated, so the points are kept on the stack
+    let points = position.get_points_of(color).collect::<SmallVec<[u8; 12]>>();
+
+    let blocked_stones = points.iter().filter(|&&point| position.get_free_neighbors(point).is_empty()).count();
+    let open_mills = MILLS
+        .iter()
+        .filter(|mill| {
+            let stones = mill.iter().filter(|&&point| is_color(point)).count();
+            let free_points = mill.iter().filter(|&&point| position.get_stone(point).is_none()).count();
+            stones == 2 && free_points == 1
+        })
+        .count();
+    let double_mills = points
+        .iter()
+        .filter(|&&point| position.is_in_mill(point, color))
+        .filter(|&&point| {
+            position.get_free_neighbors(point).into_iter().any(|neighbor| {
+                let mut simulation = *position;
+                simulation.set_stone(point, None);
+                simulation.set_stone(neighbor, Some(color));
+                simulation.is_in_mill(neighbor, color)
+            })
+        })
+        .count();
+
+    let mut terms = [0; TERM_COUNT];
+    for (term, count) in [
+        (Term::Stones, points.len() as i32),
+        (Term::StonesInHand, position.get_stones_in_hand(color) as i32),
+        (Term::Mobility, moves_possible as i32),
+        (Term::MovesIntoMill, moves_into_mill as i32),
+        (Term::TakeableStones, stones_to_take as i32),
+        (Term::BlockedStones, blocked_stones as i32),
+        (Term::OpenMills, open_mills as i32),
+        (Term::DoubleMills, double_mills as i32),
+    ] {
+        terms[term.index()] = count;
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Phase, Term, Weights};
+    use crate::game::engine::testing::engine_position;
+
+    #[test]
+    fn weights_are_parsed_over_the_defaults() {
+        let weights = "# Jumping stones are worth more\n\nstones 100 100 150\n".parse::<Weights>().unwrap();
+        assert_eq!(weights.get_weight(Term::Stones, Phase::Jump), 150);
+        assert_eq!(
+            weights.get_weight(Term::Mobility, Phase::Move),
+            Weights::default().get_weight(Term::Mobility, Phase::Move)
+        );
+
+        assert_eq!(Weights::default().to_string().parse::<Weights>().unwrap(), Weights::default());
+        assert!("stones 100 100".parse::<Weights>().is_err());
+        assert!("stone 100 100 100".parse::<Weights>().is_err());
+        assert!("stones 100 100 x".parse::<Weights>().is_err());
+    }
+
+    #[test]
+    fn breakdown_explains_the_score() {
+        // White can close a mill on the inner ring & another one by sliding G4 out of the closed mill on the outer
+        // ring to F4 & back, which makes it a double mill. Both players have a blocked stone.
+        let position = engine_position("BWWEEEEBBWEWEEEEEWWWEEBE w m 0 0 0 20");
+        let weights = Weights::default();
+        let breakdown = weights.explain(&position);
+
+        let term = |term: Term| *breakdown.terms.iter().find(|term_score| term_score.term == term).unwrap();
+        assert_eq!(breakdown.phase, Phase::Move);
+        assert_eq!((term(Term::Stones).own, term(Term::Stones).opponent), (7, 4));
+        assert_eq!((term(Term::BlockedStones).own, term(Term::BlockedStones).opponent), (1, 1));
+        assert_eq!(term(Term::OpenMills).own, 3);
+        assert_eq!(term(Term::DoubleMills).own, 1);
+        assert_eq!(breakdown.get_score(), weights.evaluate(&position));
+    }
+}
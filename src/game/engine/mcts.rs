@@ -0,0 +1,292 @@
+//! Contains the [MonteCarloTreeSearch] on the [EnginePosition], which rates moves by the outcome of games played to
+//! the end from them instead of by evaluating positions.
+//!
+//! Every iteration selects a path through the tree by the UCT formula, adds one untried move at its end & plays a
+//! game from there, whose result is counted on the path. The playouts either play random moves or are guided by
+//! [get_set_triple], which lets them close mills & prevent the ones of the opponent like a player would.
+
+use std::time::{Duration, Instant};
+
+use mill_playfield::PlayerColor;
+use nanorand::{Rng, WyRand};
+
+use super::{EngineMove, EnginePosition, Outcome};
+use crate::game::efficient_state::get_set_triple;
+use crate::game::moves::Move;
+
+/// The exploration constant of the UCT formula, which is the theoretical optimum of √2
+pub const DEFAULT_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+/// Playouts still running after this amount of plies are counted as draws, as the stones might circle forever
+pub const DEFAULT_MAX_PLAYOUT_PLIES: u32 = 200;
+
+/// How long the search runs
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MctsBudget {
+    Iterations(u32),
+    /// The time is checked after every iteration, at least one iteration is always run
+    Time(Duration),
+}
+
+/// How the moves of the playouts are chosen
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Playout {
+    Random,
+    /// Closes mills if possible, otherwise prefers the moves leaving the opponent the least moves into a mill,
+    /// which are counted by [get_set_triple] while the opponent has stones left to place & by
+    /// [crate::game::efficient_state::get_move_triple] afterwards. Ties are broken randomly.
+    Guided,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MctsConfig {
+    pub budget: MctsBudget,
+    pub playout: Playout,
+    pub exploration: f64,
+    pub max_playout_plies: u32,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            budget: MctsBudget::Iterations(10_000),
+            playout: Playout::Guided,
+            exploration: DEFAULT_EXPLORATION,
+            max_playout_plies: DEFAULT_MAX_PLAYOUT_PLIES,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MctsResult {
+    /// The move visited most often, which is the most robust choice
+    pub best_move: Move,
+    pub visits: u32,
+    /// The share of points the best move scored in its playouts, counting draws as half a point
+    pub win_rate: f64,
+    pub iterations: u32,
+}
+
+struct Node {
+    position: EnginePosition,
+    // The move leading to the node, which doesn't exist for the root
+    r#move: Option<EngineMove>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried_moves: Vec<EngineMove>,
+    outcome: Option<Outcome>,
+    visits: u32,
+    // The points scored by the player who made the move leading to the node
+    points: f64,
+}
+
+impl Node {
+    fn new(position: EnginePosition, r#move: Option<EngineMove>, parent: Option<usize>) -> Self {
+        let mut untried_moves = position.generate_moves();
+        let outcome = position.get_outcome(&untried_moves);
+        if outcome.is_some() {
+            untried_moves.clear();
+        }
+
+        Node {
+            position,
+            r#move,
+            parent,
+            children: Vec::new(),
+            untried_moves,
+            outcome,
+            visits: 0,
+            points: 0.0,
+        }
+    }
+}
+
+pub struct MonteCarloTreeSearch {
+    config: MctsConfig,
+    rng: WyRand,
+}
+
+impl MonteCarloTreeSearch {
+    pub fn new(config: MctsConfig) -> Self {
+        MonteCarloTreeSearch { config, rng: WyRand::new() }
+    }
+
+    /// Returns a search which plays the same playouts & therefore chooses the same moves every time it's created with
+    /// the same seed, as long as the budget is a number of iterations
+    pub fn with_seed(config: MctsConfig, seed: u64) -> Self {
+        MonteCarloTreeSearch { config, rng: WyRand::new_seed(seed) }
+    }
+
+    pub fn get_config(&self) -> &MctsConfig {
+        &self.config
+    }
+
+    /// Searches the move of the side to move until the budget is used up.
+    /// Returns nothing if the side to move has no legal move.
+    pub fn search(&mut self, position: &EnginePosition) -> Option<MctsResult> {
+        let start = Instant::now();
+        let mut tree = vec![Node::new(*position, None, None)];
+        if tree[0].untried_moves.is_empty() {
+            return None;
+        }
+
+        let mut iterations = 0;
+        loop {
+            let leaf = self.select_and_expand(&mut tree);
+            let winner = match tree[leaf].outcome {
+                Some(outcome) => Self::winner_of(&tree[leaf].position, outcome),
+                None => self.play_out(tree[leaf].position),
+            };
+            Self::backpropagate(&mut tree, leaf, winner);
+            iterations += 1;
+
+            let budget_is_used_up = match self.config.budget {
+                MctsBudget::Iterations(max_iterations) => max_iterations <= iterations,
+                MctsBudget::Time(time) => start + time <= Instant::now(),
+            };
+            if budget_is_used_up {
+                break;
+            }
+        }
+
+        let best_child = tree[0].children.iter().map(|&child| &tree[child]).max_by_key(|child| child.visits)?;
+        Some(MctsResult {
+            best_move: Move::from(best_child.r#move.unwrap()),
+            visits: best_child.visits,
+            win_rate: best_child.points / best_child.visits as f64,
+            iterations,
+        })
+    }
+
+    /// Descends from the root to the child with the best UCT value until a node has untried moves or is terminal.
+    /// One of the untried moves gets a new child node, whose index is returned.
+    fn select_and_expand(&mut self, tree: &mut Vec<Node>) -> usize {
+        let mut index = 0;
+
+        while tree[index].untried_moves.is_empty() && !tree[index].children.is_empty() {
+            let log_visits = (tree[index].visits as f64).ln();
+            let uct = |child: &Node| {
+                child.points / child.visits as f64 + self.config.exploration * (log_visits / child.visits as f64).sqrt()
+            };
+
+            index = *tree[index]
+                .children
+                .iter()
+                .max_by(|&&child, &&other_child| uct(&tree[child]).total_cmp(&uct(&tree[other_child])))
+                .unwrap();
+        }
+
+        if tree[index].untried_moves.is_empty() {
+            return index;
+        }
+
+        let untried_moves = &mut tree[index].untried_moves;
+        let r#move = untried_moves.swap_remove(self.rng.generate_range(0..untried_moves.len()));
+        let child = Node::new(tree[index].position.make_move(&r#move), Some(r#move), Some(index));
+
+        tree.push(child);
+        let child_index = tree.len() - 1;
+        tree[index].children.push(child_index);
+        child_index
+    }
+
+    /// Plays the position to its end & returns the winner, which doesn't exist for draws
+    fn play_out(&mut self, mut position: EnginePosition) -> Option<PlayerColor> {
+        for _ in 0..self.config.max_playout_plies {
+            let moves = position.generate_moves();
+            if let Some(outcome) = position.get_outcome(&moves) {
+                return Self::winner_of(&position, outcome);
+            }
+
+            let r#move = match self.config.playout {
+                Playout::Random => moves[self.rng.generate_range(0..moves.len())],
+                Playout::Guided => self.choose_guided_move(&position, &moves),
+            };
+            position = position.make_move(&r#move);
+        }
+        None
+    }
+
+    fn choose_guided_move(&mut self, position: &EnginePosition, moves: &[EngineMove]) -> EngineMove {
+        let closing_mills = moves.iter().filter(|r#move| r#move.takes[0].is_some()).copied().collect::<Vec<_>>();
+        if !closing_mills.is_empty() {
+            return closing_mills[self.rng.generate_range(0..closing_mills.len())];
+        }
+
+        let opponents_moves_into_mill = |r#move: &EngineMove| {
+            let next_position = position.make_move(r#move);
+            let mut play_field = *next_position.get_play_field();
+            let side_to_move = next_position.get_side_to_move();
+            // Counts the placements into a mill while the opponent has stones in hand, the moves into one afterwards
+            get_set_triple(&mut play_field, side_to_move, next_position.get_stones_in_hand(side_to_move)).1
+        };
+
+        let scored_moves = moves.iter().map(|r#move| (*r#move, opponents_moves_into_mill(r#move))).collect::<Vec<_>>();
+        let least_moves_into_mill = scored_moves.iter().map(|(_, score)| *score).min().unwrap();
+        let best_moves = scored_moves
+            .into_iter()
+            .filter(|(_, score)| *score == least_moves_into_mill)
+            .map(|(r#move, _)| r#move)
+            .collect::<Vec<_>>();
+
+        best_moves[self.rng.generate_range(0..best_moves.len())]
+    }
+
+    /// Counts the result for every node from the leaf up to the root. The points of a node are the ones of the player
+    /// who made the move leading to it, which is the opponent of its side to move.
+    fn backpropagate(tree: &mut [Node], leaf: usize, winner: Option<PlayerColor>) {
+        let mut index = Some(leaf);
+
+        while let Some(current) = index {
+            let node = &mut tree[current];
+            node.visits += 1;
+            node.points += match winner {
+                Some(PlayerColor::White) if matches!(node.position.get_side_to_move(), PlayerColor::Black) => 1.0,
+                Some(PlayerColor::Black) if matches!(node.position.get_side_to_move(), PlayerColor::White) => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            index = node.parent;
+        }
+    }
+
+    fn winner_of(position: &EnginePosition, outcome: Outcome) -> Option<PlayerColor> {
+        match outcome {
+            Outcome::Loss => Some(!position.get_side_to_move()),
+            Outcome::Draw => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MctsBudget, MctsConfig, MonteCarloTreeSearch, Playout};
+    use crate::game::engine::testing::{engine_position, MILL_IN_SET_PHASE, WINNING_JUMP};
+
+    #[test]
+    fn finds_the_winning_mill() {
+        // White jumps into a mill, taking one of the last three black stones
+        let position = engine_position(WINNING_JUMP);
+        let config = MctsConfig {
+            budget: MctsBudget::Iterations(500),
+            playout: Playout::Random,
+            ..Default::default()
+        };
+
+        let result = MonteCarloTreeSearch::with_seed(config, 1).search(&position).unwrap();
+        assert_eq!(result.best_move.target_field(), ('C', 5));
+        assert_eq!(result.iterations, 500);
+    }
+
+    #[test]
+    fn seeded_searches_are_deterministic() {
+        let position = engine_position(MILL_IN_SET_PHASE);
+        let config = MctsConfig {
+            budget: MctsBudget::Iterations(50),
+            max_playout_plies: 50,
+            ..Default::default()
+        };
+
+        let result = MonteCarloTreeSearch::with_seed(config, 7).search(&position).unwrap();
+        assert_eq!(MonteCarloTreeSearch::with_seed(config, 7).search(&position), Some(result));
+    }
+}
@@ -0,0 +1,375 @@
+//! Contains the [Searcher], a negamax search with alpha-beta pruning on the [EnginePosition].
+//! The search deepens iteratively until the [SearchLimit] is reached, searching the principal variation of the last
+//! iteration first, which is where most cutoffs happen.
+//! Positions searched before are looked up in the [TranspositionTable], which also knows the best move of positions
+//! only mirrored or rotated.
+//! Positions occurring for the third time are scored as draws if the rules draw on threefold repetitions, counting the
+//! positions of the game before the search as well, which is detected by their Zobrist keys. These scores depend on
+//! the way the position was reached, so they aren't stored in the [TranspositionTable].
+//! The positions at the end of the search are rated by the [Weights] of the [super::evaluation].
+
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use super::evaluation::Weights;
+use super::transposition::{Bound, TranspositionKey, TranspositionTable};
+use super::{EngineMove, EnginePosition, Outcome};
+use crate::game::moves::Move;
+
+/// The score of a won position, which is reduced by the plies it takes to win
+pub const WIN_SCORE: i32 = 100_000;
+/// Iterative deepening stops at this depth, even if there's time left
+pub const MAX_DEPTH: u32 = 64;
+
+/// The amount of nodes searched between two looks at the clock
+const NODES_PER_TIME_CHECK: u64 = 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SearchLimit {
+    /// Searches until the iteration of this depth is completed
+    Depth(u32),
+    /// Searches until the time is up, which is checked every few nodes. The first iteration is always completed, so
+    /// there's a move to return even if the time is too short for it.
+    Time(Duration),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SearchResult {
+    pub best_move: Move,
+    /// The score of the best move from the view of the side to move
+    pub score: i32,
+    /// The depth of the last completed iteration
+    pub depth: u32,
+    /// The moves both players are expected to play, starting with the best move
+    pub principal_variation: Vec<Move>,
+    pub nodes: u64,
+}
+
+impl SearchResult {
+    /// Returns the amount of plies until the side to move wins (positive) or loses (negative), if the score is one of
+    /// a decided game
+    pub fn plies_to_win(&self) -> Option<i32> {
+        match self.score {
+            score if WIN_SCORE - (MAX_DEPTH as i32) <= score => Some(WIN_SCORE - score),
+            score if score <= -WIN_SCORE + (MAX_DEPTH as i32) => Some(-WIN_SCORE - score),
+            _ => None,
+        }
+    }
+}
+
+/// Prints the depth, the score or the plies until the game is decided & the principal variation,
+/// e.g. `depth 4 score 112 nodes 3021 pv d1 a1 g1xa1 g7`
+impl Display for SearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("depth {} ", self.depth))?;
+        match self.plies_to_win() {
+            Some(plies) if 0 < plies => f.write_fmt(format_args!("win in {plies} plies "))?,
+            Some(plies) => f.write_fmt(format_args!("loss in {} plies ", -plies))?,
+            None => f.write_fmt(format_args!("score {} ", self.score))?,
+        }
+        f.write_fmt(format_args!("nodes {} pv", self.nodes))?;
+
+        for r#move in &self.principal_variation {
+            f.write_fmt(format_args!(" {}", r#move))?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Searcher {
+    nodes: u64,
+    deadline: Option<Instant>,
+    aborted: bool,
+    // The Zobrist keys of the positions of the game before the root
+    history: Vec<u64>,
+    // The Zobrist keys of the history & of the positions on the way from the root to the current one
+    path: Vec<u64>,
+    // The amount of positions scored as draws by repetition, so the scores depending on them aren't stored
+    repetitions: u64,
+    // Kept between the searches, as the positions of the next move mostly were searched already
+    transposition_table: TranspositionTable,
+    weights: Weights,
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Searcher::with_table_size(super::transposition::DEFAULT_SIZE)
+    }
+}
+
+impl Searcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a searcher with a transposition table of the specified amount of entries
+    pub fn with_table_size(size: usize) -> Self {
+        Searcher {
+            nodes: 0,
+            deadline: None,
+            aborted: false,
+            history: Vec::new(),
+            path: Vec::new(),
+            repetitions: 0,
+            transposition_table: TranspositionTable::with_size(size),
+            weights: Weights::default(),
+        }
+    }
+
+    pub fn get_weights(&self) -> &Weights {
+        &self.weights
+    }
+
+    /// Evaluates the positions by the weights from now on. The transposition table is cleared, as its scores were
+    /// evaluated by the former weights.
+    pub fn set_weights(&mut self, weights: Weights) {
+        self.weights = weights;
+        self.transposition_table.clear();
+    }
+
+    /// Counts the positions of the game before the position searched next for the threefold repetitions, which are
+    /// passed in the order they occurred, without the searched one
+    pub fn set_history(&mut self, history: &[EnginePosition]) {
+        self.history = history.iter().map(EnginePosition::get_zobrist_key).collect();
+    }
+
+    /// Searches the best move of the side to move, deepening iteratively until the limit is reached or the game is
+    /// decided. Returns nothing if the side to move has no legal move.
+    pub fn search(&mut self, position: &EnginePosition, limit: SearchLimit) -> Option<SearchResult> {
+        let start = Instant::now();
+        let max_depth = match limit {
+            SearchLimit::Depth(depth) => depth.clamp(1, MAX_DEPTH),
+            SearchLimit::Time(_) => MAX_DEPTH,
+        };
+
+        self.nodes = 0;
+        self.aborted = false;
+        self.transposition_table.new_search();
+        let mut completed: Option<(i32, u32, Vec<EngineMove>)> = None;
+
+        for depth in 1..=max_depth {
+            // The first iteration must complete, so the time only counts from the second one on
+            self.deadline = match limit {
+                SearchLimit::Time(time) if 1 < depth => Some(start + time),
+                _ => None,
+            };
+
+            self.path.clone_from(&self.history);
+            let hint = completed.as_ref().map(|(_, _, principal_variation)| principal_variation.clone());
+            let mut principal_variation = Vec::new();
+            let score =
+                self.negamax(position, depth, 0, -WIN_SCORE, WIN_SCORE, hint.as_deref(), &mut principal_variation);
+
+            if self.aborted || principal_variation.is_empty() {
+                break;
+            }
+            completed = Some((score, depth, principal_variation));
+
+            // A decided game doesn't get any better by searching deeper
+            if WIN_SCORE - (MAX_DEPTH as i32) <= score.abs() {
+                break;
+            }
+        }
+
+        let (score, depth, principal_variation) = completed?;
+        let principal_variation = principal_variation.into_iter().map(Move::from).collect::<Vec<_>>();
+        Some(SearchResult {
+            best_move: principal_variation[0].clone(),
+            score,
+            depth,
+            principal_variation,
+            nodes: self.nodes,
+        })
+    }
+
+    /// Returns the score of the position from the view of the side to move within the window of alpha & beta.
+    /// The best line found is written into the principal variation, which the hint is the one of the last iteration
+    /// for, if the position is part of it.
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        position: &EnginePosition,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        hint: Option<&[EngineMove]>,
+        principal_variation: &mut Vec<EngineMove>,
+    ) -> i32 {
+        self.nodes += 1;
+        let time_is_up = || self.deadline.is_some_and(|deadline| deadline <= Instant::now());
+        if self.nodes.is_multiple_of(NODES_PER_TIME_CHECK) && time_is_up() {
+            self.aborted = true;
+        }
+        if self.aborted {
+            return 0;
+        }
+
+        let key = position.get_zobrist_key();
+        // The root is always searched, as there has to be a move to return
+        if 0 < ply && self.is_threefold_repetition(position) {
+            self.repetitions += 1;
+            return 0;
+        }
+
+        let mut moves = position.generate_moves();
+        match position.get_outcome(&moves) {
+            // Losing sooner is worse than losing later
+            Some(Outcome::Loss) => return -WIN_SCORE + ply as i32,
+            Some(Outcome::Draw) => return 0,
+            None if depth == 0 => return self.weights.evaluate(position),
+            None => (),
+        }
+
+        // Computing the key takes all symmetries of the position, so it's only done once for probing & storing
+        let transposition_key = TranspositionKey::of(position);
+        let entry = self.transposition_table.probe(&transposition_key);
+        if let Some(entry) = entry.filter(|entry| depth <= entry.depth && 0 < ply) {
+            let score = score_from_table(entry.score, ply);
+            let is_cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => beta <= score,
+                Bound::Upper => score <= alpha,
+            };
+
+            if is_cutoff {
+                principal_variation.extend(entry.best_move);
+                return score;
+            }
+        }
+
+        let principal_move = hint.and_then(|hint| hint.first()).copied();
+        Self::order_moves(&mut moves, principal_move.or(entry.and_then(|entry| entry.best_move)));
+        let original_alpha = alpha;
+        let original_repetitions = self.repetitions;
+
+        self.path.push(key);
+        let mut child_principal_variation = Vec::new();
+        for (index, r#move) in moves.iter().enumerate() {
+            // Only the first move continues the principal variation of the last iteration
+            let child_hint = match index {
+                0 => hint.filter(|hint| hint.first() == Some(r#move)).map(|hint| &hint[1..]),
+                _ => None,
+            };
+
+            child_principal_variation.clear();
+            let score = -self.negamax(
+                &position.make_move(r#move),
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+                child_hint,
+                &mut child_principal_variation,
+            );
+            if self.aborted {
+                return 0;
+            }
+
+            if alpha < score {
+                alpha = score;
+                principal_variation.clear();
+                principal_variation.push(*r#move);
+                principal_variation.extend_from_slice(&child_principal_variation);
+            }
+            if beta <= alpha {
+                break;
+            }
+        }
+
+        self.path.pop();
+
+        let bound = match alpha {
+            _ if alpha <= original_alpha => Bound::Upper,
+            _ if beta <= alpha => Bound::Lower,
+            _ => Bound::Exact,
+        };
+        let best_move = principal_variation.first().copied();
+        if self.repetitions == original_repetitions {
+            self.transposition_table.store(&transposition_key, depth, score_to_table(alpha, ply), bound, best_move);
+        }
+
+        // Every move failed low, which still has to return a move at the root
+        if principal_variation.is_empty() && ply == 0 {
+            principal_variation.push(moves[0]);
+        }
+        alpha
+    }
+
+    /// Returns if the position occurred twice before on the path, if the rules draw on threefold repetitions
+    fn is_threefold_repetition(&self, position: &EnginePosition) -> bool {
+        let key = position.get_zobrist_key();
+        position.get_rules().draw_conditions.threefold_repetition
+            && 2 <= self.path.iter().filter(|&&path_key| path_key == key).count()
+    }
+
+    /// Sorts the move of the principal variation to the front, followed by the moves taking stones
+    fn order_moves(moves: &mut [EngineMove], principal_move: Option<EngineMove>) {
+        moves.sort_by_key(|r#move| (Some(*r#move) != principal_move, r#move.takes[0].is_none()));
+    }
+}
+
+/// Scores of decided games are stored relative to the position, as it might be reached after another amount of plies
+fn score_to_table(score: i32, ply: u32) -> i32 {
+    match score {
+        score if WIN_SCORE - (MAX_DEPTH as i32) <= score => score + ply as i32,
+        score if score <= -WIN_SCORE + (MAX_DEPTH as i32) => score - ply as i32,
+        score => score,
+    }
+}
+
+fn score_from_table(score: i32, ply: u32) -> i32 {
+    match score {
+        score if WIN_SCORE - (MAX_DEPTH as i32) <= score => score - ply as i32,
+        score if score <= -WIN_SCORE + (MAX_DEPTH as i32) => score + ply as i32,
+        score => score,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchLimit, Searcher};
+    use crate::game::engine::testing::{engine_position, MILL_IN_SET_PHASE, WINNING_JUMP};
+    use crate::game::engine::EnginePosition;
+    use crate::game::position::Position;
+    use crate::game::rules::RuleSet;
+
+    #[test]
+    fn closes_mills() {
+        let position = engine_position(MILL_IN_SET_PHASE);
+        let result = Searcher::new().search(&position, SearchLimit::Depth(2)).unwrap();
+
+        assert_eq!(result.best_move.target_field(), ('C', 5));
+        assert_eq!(result.principal_variation.len(), 2);
+        assert!(0 < result.score);
+    }
+
+    #[test]
+    fn finds_forced_wins() {
+        // White jumps into a mill, taking one of the last three black stones
+        let position = engine_position(WINNING_JUMP);
+        let result = Searcher::new().search(&position, SearchLimit::Depth(4)).unwrap();
+
+        assert_eq!(result.best_move.target_field(), ('C', 5));
+        assert_eq!(result.plies_to_win(), Some(1));
+    }
+
+    #[test]
+    fn repetitions_count_the_history() {
+        let position = Position::from_notation(MILL_IN_SET_PHASE, RuleSet::tournament()).unwrap();
+        let position = EnginePosition::try_from(&position).unwrap();
+        let children = position.generate_moves().iter().map(|r#move| position.make_move(r#move)).collect::<Vec<_>>();
+        let mut searcher = Searcher::new();
+
+        // Every move reaches a position of the game for the second time, which doesn't draw yet
+        searcher.set_history(&children);
+        assert!(0 < searcher.search(&position, SearchLimit::Depth(1)).unwrap().score);
+
+        // ... but for the third time it does. The draws aren't stored, as they depend on the history.
+        searcher.set_history(&[children.clone(), children].concat());
+        assert_eq!(searcher.search(&position, SearchLimit::Depth(1)).unwrap().score, 0);
+
+        searcher.set_history(&[]);
+        assert!(0 < searcher.search(&position, SearchLimit::Depth(1)).unwrap().score);
+    }
+}
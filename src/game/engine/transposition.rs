@@ -0,0 +1,256 @@
+//! Contains the [TranspositionTable] of the [super::search::Searcher], which remembers the scores of positions
+//! searched before, no matter by which moves they were reached.
+//!
+//! Positions are stored under their [zobrist::symmetric_key], so the mirrored & rotated variants of a position share
+//! one entry. The best move of an entry is stored for the variant the key is the one of & translated back through the
+//! [Symmetry] which maps the probed position onto it.
+
+use super::{zobrist, EngineMove, EnginePosition, POINT_COUNT};
+
+/// The amount of entries of the table created by [TranspositionTable::default], which takes a few megabytes
+pub const DEFAULT_SIZE: usize = 1 << 18;
+
+/// One of the 16 symmetries of the board, which mirrors the board first, rotates it clockwise afterwards & swaps the
+/// inner & the outer ring at last
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Symmetry {
+    pub mirrored: bool,
+    /// The amount of quarter turns
+    pub rotation: u8,
+    pub rings_swapped: bool,
+}
+
+impl Symmetry {
+    pub const IDENTITY: Symmetry = Symmetry { mirrored: false, rotation: 0, rings_swapped: false };
+    pub const COUNT: usize = 16;
+
+    /// Returns every symmetry, starting with the [Symmetry::IDENTITY]
+    pub fn all() -> impl Iterator<Item = Symmetry> {
+        (0..Self::COUNT as u8).map(|bits: u8| Symmetry {
+            mirrored: bits & 1 == 1,
+            rotation: (bits >> 1) & 3,
+            rings_swapped: 8 <= bits,
+        })
+    }
+
+    /// Returns the point the symmetry maps the point to
+    pub fn apply(&self, point: u8) -> u8 {
+        let (mut ring_index, mut index) = (point / 8, point % 8);
+
+        // The mirror axis goes through the top & bottom middle fields
+        if self.mirrored {
+            index = (8 - index) % 8;
+        }
+        index = (index + 2 * self.rotation) % 8;
+        if self.rings_swapped {
+            ring_index = 2 - ring_index;
+        }
+        ring_index * 8 + index
+    }
+
+    /// Returns the point which the symmetry maps onto the point
+    pub fn revert(&self, point: u8) -> u8 {
+        let (mut ring_index, mut index) = (point / 8, point % 8);
+
+        if self.rings_swapped {
+            ring_index = 2 - ring_index;
+        }
+        index = (index + 8 - 2 * self.rotation) % 8;
+        if self.mirrored {
+            index = (8 - index) % 8;
+        }
+        ring_index * 8 + index
+    }
+
+    pub fn apply_to_move(&self, r#move: &EngineMove) -> EngineMove {
+        self.map_move(r#move, |point| self.apply(point))
+    }
+
+    pub fn revert_move(&self, r#move: &EngineMove) -> EngineMove {
+        self.map_move(r#move, |point| self.revert(point))
+    }
+
+    /// Returns the position with all stones moved to the points the symmetry maps them to
+    pub fn apply_to_position(&self, position: &EnginePosition) -> EnginePosition {
+        let mut transformed_position = *position;
+        for point in 0..POINT_COUNT {
+            transformed_position.set_stone(point, None);
+        }
+        for point in 0..POINT_COUNT {
+            if let Some(stone) = position.get_stone(point) {
+                transformed_position.set_stone(self.apply(point), Some(stone));
+            }
+        }
+        transformed_position
+    }
+
+    fn map_move(&self, r#move: &EngineMove, map: impl Fn(u8) -> u8) -> EngineMove {
+        EngineMove {
+            start: r#move.start.map(&map),
+            target: map(r#move.target),
+            jump: r#move.jump,
+            takes: r#move.takes.map(|take| take.map(&map)),
+        }
+    }
+}
+
+/// The key a position is stored under, which is computed once per position & used for probing & storing it
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TranspositionKey {
+    /// The [zobrist::symmetric_key] of the position, which the table is indexed by
+    pub zobrist_key: u64,
+    /// The symmetry mapping the position onto the variant the key is the one of
+    pub symmetry: Symmetry,
+}
+
+impl TranspositionKey {
+    pub fn of(position: &EnginePosition) -> Self {
+        let (zobrist_key, symmetry) = zobrist::symmetric_key_with_symmetry(position);
+        TranspositionKey { zobrist_key, symmetry }
+    }
+}
+
+/// The kind of the score of an entry, which depends on the window the position was searched with
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Bound {
+    /// The score is the exact one
+    Exact,
+    /// The score is at least the one stored, as a move was good enough for a cutoff
+    Lower,
+    /// The score is at most the one stored, as no move was better than alpha
+    Upper,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Entry {
+    /// The [zobrist::symmetric_key] of the position stored
+    pub zobrist_key: u64,
+    pub depth: u32,
+    pub score: i32,
+    pub bound: Bound,
+    /// The best move, which only exists if a move was better than alpha. It's stored for the variant of the key &
+    /// translated back to the probed position.
+    pub best_move: Option<EngineMove>,
+    // The search the entry was stored in, so entries of former searches are replaced first
+    generation: u32,
+}
+
+/// A table of a fixed amount of entries, indexed by the symmetric Zobrist key. Entries of the same index are
+/// replaced if they were stored in a former search or searched less deep.
+pub struct TranspositionTable {
+    entries: Vec<Option<Entry>>,
+    generation: u32,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::with_size(DEFAULT_SIZE)
+    }
+}
+
+impl TranspositionTable {
+    /// Returns a table of the specified amount of entries, which is rounded up to the next power of two
+    pub fn with_size(size: usize) -> Self {
+        TranspositionTable {
+            entries: vec![None; size.max(1).next_power_of_two()],
+            generation: 0,
+        }
+    }
+
+    /// Marks the entries stored until now as the ones of a former search
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+
+    /// Returns the entry stored for the position of the key, with its best move translated back to the position
+    pub fn probe(&self, key: &TranspositionKey) -> Option<Entry> {
+        let entry = self.entries[self.index_of(key)].filter(|entry| entry.zobrist_key == key.zobrist_key)?;
+        Some(Entry {
+            best_move: entry.best_move.map(|r#move| key.symmetry.revert_move(&r#move)),
+            ..entry
+        })
+    }
+
+    /// Stores the search result of the position of the key, unless an entry searched deeper in the current search has
+    /// the same index
+    pub fn store(
+        &mut self,
+        key: &TranspositionKey,
+        depth: u32,
+        score: i32,
+        bound: Bound,
+        best_move: Option<EngineMove>,
+    ) {
+        let index = self.index_of(key);
+
+        if let Some(entry) = self.entries[index] {
+            if entry.generation == self.generation && depth < entry.depth {
+                return;
+            }
+        }
+
+        self.entries[index] = Some(Entry {
+            zobrist_key: key.zobrist_key,
+            depth,
+            score,
+            bound,
+            best_move: best_move.map(|r#move| key.symmetry.apply_to_move(&r#move)),
+            generation: self.generation,
+        });
+    }
+
+    fn index_of(&self, key: &TranspositionKey) -> usize {
+        key.zobrist_key as usize & (self.entries.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bound, Symmetry, TranspositionKey, TranspositionTable};
+    use crate::game::engine::testing::{engine_position, MILL_IN_SET_PHASE};
+    use crate::game::engine::EngineMove;
+
+    #[test]
+    fn symmetries_are_reverted() {
+        for symmetry in Symmetry::all() {
+            assert!((0..24).all(|point| symmetry.revert(symmetry.apply(point)) == point));
+        }
+        assert_eq!(Symmetry::all().filter(|symmetry| symmetry.apply(1) == 1).count(), 2);
+    }
+
+    #[test]
+    fn mirrored_positions_share_their_entry() {
+        let position = engine_position(MILL_IN_SET_PHASE);
+        let mirrored = Symmetry { mirrored: true, rotation: 1, rings_swapped: true }.apply_to_position(&position);
+        // Closing the mill on C5 & taking the black stone on D6
+        let r#move = EngineMove { start: None, target: 7, jump: false, takes: [Some(8), None] };
+
+        let mut transposition_table = TranspositionTable::with_size(16);
+        let key = TranspositionKey::of(&position);
+        transposition_table.store(&key, 2, 120, Bound::Exact, Some(r#move));
+
+        let entry = transposition_table.probe(&TranspositionKey::of(&mirrored)).unwrap();
+        assert_eq!(entry.score, 120);
+        assert!(mirrored.generate_moves().contains(&entry.best_move.unwrap()));
+        assert_eq!(transposition_table.probe(&key).unwrap().best_move, Some(r#move));
+    }
+
+    #[test]
+    fn deeper_entries_are_kept() {
+        let position = engine_position(MILL_IN_SET_PHASE);
+        let key = TranspositionKey::of(&position);
+        let mut transposition_table = TranspositionTable::with_size(1);
+
+        transposition_table.store(&key, 4, 100, Bound::Lower, None);
+        transposition_table.store(&key, 2, 50, Bound::Exact, None);
+        assert_eq!(transposition_table.probe(&key).unwrap().depth, 4);
+
+        transposition_table.new_search();
+        transposition_table.store(&key, 2, 50, Bound::Exact, None);
+        assert_eq!(transposition_table.probe(&key).unwrap().depth, 2);
+    }
+}
@@ -0,0 +1,153 @@
+//! Contains the Zobrist keys of [EnginePosition]s, which are the XOR of a random number for every stone on the board,
+//! for the side to move & for the amount of stones in each hand.
+//! Placing, sliding, jumping & taking only XOR the numbers of the changed parts into the key, which is what the
+//! [EnginePosition] does on every change, so its key is always up to date without hashing the whole position.
+//!
+//! The [symmetric_key] is the smallest key of all mirrored & rotated variants of a position, so they share it.
+//! The position keeps the keys of all its variants up to date the same way, which only XORs the stone keys of the
+//! points the [Symmetry]s map the changed points to.
+
+use mill_playfield::PlayerColor;
+use nanorand::{Rng, WyRand};
+use once_cell::sync::Lazy;
+
+use super::transposition::Symmetry;
+use super::{EnginePosition, POINT_COUNT};
+
+/// The most stones a hand can hold, which is enough for the presets of [crate::game::rules::RuleSet]
+pub const MAX_STONES_IN_HAND: usize = 12;
+
+// The keys are always the same, so they might be stored along with positions
+const SEED: u64 = 0x6d69_6c6c;
+
+pub static ZOBRIST_KEYS: Lazy<ZobristKeys> = Lazy::new(ZobristKeys::generate);
+
+pub struct ZobristKeys {
+    // first one: white, second one: black
+    stones: [[u64; POINT_COUNT as usize]; 2],
+    // The keys of the stones on the points every symmetry maps the points to, ordered like Symmetry::all
+    symmetric_stones: [[[u64; Symmetry::COUNT]; POINT_COUNT as usize]; 2],
+    // White to move is the key 0
+    black_to_move: u64,
+    stones_in_hand: [[u64; MAX_STONES_IN_HAND + 1]; 2],
+}
+
+impl ZobristKeys {
+    fn generate() -> Self {
+        let mut rng = WyRand::new_seed(SEED);
+        let mut keys = ZobristKeys {
+            stones: [[0; POINT_COUNT as usize]; 2],
+            symmetric_stones: [[[0; Symmetry::COUNT]; POINT_COUNT as usize]; 2],
+            black_to_move: rng.generate(),
+            stones_in_hand: [[0; MAX_STONES_IN_HAND + 1]; 2],
+        };
+
+        keys.stones.iter_mut().flatten().for_each(|key| *key = rng.generate());
+        keys.stones_in_hand.iter_mut().flatten().for_each(|key| *key = rng.generate());
+
+        for (color_keys, symmetric_color_keys) in keys.stones.iter().zip(keys.symmetric_stones.iter_mut()) {
+            for (point, symmetric_point_keys) in symmetric_color_keys.iter_mut().enumerate() {
+                for (key, symmetry) in symmetric_point_keys.iter_mut().zip(Symmetry::all()) {
+                    *key = color_keys[symmetry.apply(point as u8) as usize];
+                }
+            }
+        }
+        keys
+    }
+
+    pub fn stone(&self, point: u8, color: PlayerColor) -> u64 {
+        self.stones[color_index(color)][point as usize]
+    }
+
+    /// Returns the keys of the stone on the points every symmetry maps the point to, ordered like [Symmetry::all]
+    pub fn symmetric_stones(&self, point: u8, color: PlayerColor) -> &[u64; Symmetry::COUNT] {
+        &self.symmetric_stones[color_index(color)][point as usize]
+    }
+
+    pub fn side_to_move(&self, color: PlayerColor) -> u64 {
+        match color {
+            PlayerColor::White => 0,
+            PlayerColor::Black => self.black_to_move,
+        }
+    }
+
+    /// Panics if the amount is larger than [MAX_STONES_IN_HAND]
+    pub fn stones_in_hand(&self, color: PlayerColor, amount: u32) -> u64 {
+        self.stones_in_hand[color_index(color)][amount as usize]
+    }
+}
+
+/// Computes the key of the position from scratch
+pub fn compute_key(position: &EnginePosition) -> u64 {
+    compute_key_with(position, Symmetry::IDENTITY)
+}
+
+/// Computes the keys of the position & its variants from scratch, ordered like [Symmetry::all], which the position
+/// keeps up to date on its own afterwards
+pub fn compute_symmetric_keys(position: &EnginePosition) -> [u64; Symmetry::COUNT] {
+    let mut keys = [0; Symmetry::COUNT];
+    for (key, symmetry) in keys.iter_mut().zip(Symmetry::all()) {
+        *key = compute_key_with(position, symmetry);
+    }
+    keys
+}
+
+/// Returns the smallest key of the position & its mirrored & rotated variants, which are the same for the search.
+/// Other than comparing the canonical forms, comparing the symmetric keys only compares two numbers.
+pub fn symmetric_key(position: &EnginePosition) -> u64 {
+    symmetric_key_with_symmetry(position).0
+}
+
+/// Returns the [symmetric_key] of the position & the symmetry mapping the position onto the variant it's the key of
+pub fn symmetric_key_with_symmetry(position: &EnginePosition) -> (u64, Symmetry) {
+    position.symmetric_keys.iter().copied().zip(Symmetry::all()).min_by_key(|(key, _)| *key).unwrap()
+}
+
+/// Computes the key of the position the symmetry maps the position to
+fn compute_key_with(position: &EnginePosition, symmetry: Symmetry) -> u64 {
+    let keys = &ZOBRIST_KEYS;
+    let mut key = keys.side_to_move(position.side_to_move);
+
+    for color in [PlayerColor::White, PlayerColor::Black] {
+        key ^= keys.stones_in_hand(color, position.get_stones_in_hand(color));
+        for point in position.get_points_of(color) {
+            key ^= keys.stone(symmetry.apply(point), color);
+        }
+    }
+    key
+}
+
+fn color_index(color: PlayerColor) -> usize {
+    match color {
+        PlayerColor::White => 0,
+        PlayerColor::Black => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute_key, compute_symmetric_keys, symmetric_key};
+    use crate::game::engine::testing::{engine_position, MILL_IN_SET_PHASE};
+    use crate::game::engine::transposition::Symmetry;
+
+    #[test]
+    fn keys_are_updated_incrementally() {
+        let mut position = engine_position(MILL_IN_SET_PHASE);
+
+        for _ in 0..6 {
+            let r#move = position.generate_moves()[0];
+            position = position.make_move(&r#move);
+            assert_eq!(position.get_zobrist_key(), compute_key(&position));
+            assert_eq!(position.symmetric_keys, compute_symmetric_keys(&position));
+        }
+    }
+
+    #[test]
+    fn symmetric_positions_share_their_key() {
+        let position = engine_position(MILL_IN_SET_PHASE);
+        let mirrored = Symmetry { mirrored: true, rotation: 3, rings_swapped: false }.apply_to_position(&position);
+
+        assert_ne!(mirrored.get_zobrist_key(), position.get_zobrist_key());
+        assert_eq!(symmetric_key(&mirrored), symmetric_key(&position));
+    }
+}
@@ -0,0 +1,396 @@
+//! Contains the [Game], the state machine running a game from the set phase until it terminates without doing any I/O.
+//! It accepts complete [Move]s as well as moves whose stones to take are passed one by one afterwards, detects the
+//! end of the game & reports its [GameResult].
+
+use mill_playfield::PlayerColor;
+
+use crate::game::moves::{Move, Takes};
+use crate::game::position::Position;
+use crate::game::result::{DrawReason, GameResult, WinReason};
+use crate::game::rules::RuleSet;
+use crate::game::state::{PlayField, PlayFieldError};
+use crate::game::{Field, GamePhase};
+
+/// A game on the [RuleSet] it was created with, which takes the moves of the side to move until it terminates.
+/// Moves closing a mill are kept pending until the stones to take are known.
+pub struct Game {
+    position: Position,
+    // The positions after every move, including the starting one, to detect repetitions. They're kept without the
+    // history of their play field, which would make every position as large as the whole game.
+    position_history: Vec<Position>,
+    pending_move: Option<PendingMove>,
+    result: Option<GameResult>,
+}
+
+/// A move closing at least one mill, which waits for the stones to take being passed by [Game::take]
+struct PendingMove {
+    r#move: Move,
+    takes: Takes,
+    // The play field after placing or moving the stone & taking the stones taken so far
+    simulation: PlayField,
+    takes_left: usize,
+}
+
+/// A new game under the default [RuleSet]
+impl Default for Game {
+    fn default() -> Self {
+        Game::new(RuleSet::default())
+    }
+}
+
+impl Game {
+    /// Returns a game starting in the set phase with white to move, on which the specified rules apply
+    pub fn new(rules: RuleSet) -> Self {
+        Game::from_position(Position::new(rules))
+    }
+
+    /// Returns a game continuing from the specified position, which might already be decided.
+    /// A position in the [GamePhase::Start] is continued in the set phase.
+    pub fn from_position(mut position: Position) -> Self {
+        if let GamePhase::Start = position.game_phase {
+            position.game_phase = GamePhase::Set;
+        }
+
+        let mut game = Game {
+            position_history: vec![position.clone_without_history()],
+            position,
+            pending_move: None,
+            result: None,
+        };
+        game.update_result();
+        game
+    }
+
+    pub fn get_position(&self) -> &Position {
+        &self.position
+    }
+
+    pub fn get_rules(&self) -> &RuleSet {
+        self.position.get_rules()
+    }
+
+    /// Returns the positions after every move, starting with the first position of the game & ending with the current
+    /// one
+    pub fn get_position_history(&self) -> &[Position] {
+        &self.position_history
+    }
+
+    pub fn get_phase(&self) -> GamePhase {
+        self.position.game_phase
+    }
+
+    pub fn get_side_to_move(&self) -> PlayerColor {
+        self.position.side_to_move
+    }
+
+    /// Returns the result of the game, which only exists after it terminated
+    pub fn get_result(&self) -> Option<GameResult> {
+        self.result
+    }
+
+    /// Returns the last move applied to the game, including the stones taken by it
+    pub fn get_last_move(&self) -> Option<&Move> {
+        self.position.play_field.get_history().last().map(|(r#move, _)| r#move)
+    }
+
+    /// Returns the move waiting for the stones to take, without the ones taken so far
+    pub fn get_pending_move(&self) -> Option<&Move> {
+        self.pending_move.as_ref().map(|pending_move| &pending_move.r#move)
+    }
+
+    /// Returns the amount of stones the side to move still has to take before the pending move is applied
+    pub fn get_pending_takes(&self) -> usize {
+        self.pending_move.as_ref().map_or(0, |pending_move| pending_move.takes_left)
+    }
+
+    /// Returns the play field as it looks like after the pending move & the stones taken so far, which is the one
+    /// the closed mills & the takeable stones should be looked up on
+    pub fn get_pending_play_field(&self) -> Option<&PlayField> {
+        self.pending_move.as_ref().map(|pending_move| &pending_move.simulation)
+    }
+
+    /// Returns every legal [Move] of the side to move, which are none if the game terminated or a move is pending
+    pub fn generate_moves(&self) -> Vec<Move> {
+        match (&self.result, &self.pending_move) {
+            (None, None) => self.position.generate_moves(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Makes the move of the side to move. If it closes mills without specifying the stones to take, it's kept
+    /// pending until they were passed to [Game::take], unless there's nothing the player could take.
+    /// Otherwise, the move is applied at once & the result is updated.
+    ///
+    /// Handled extreme cases additionally to the ones of [Position::apply_move]:
+    /// - The stones to take of the last move weren't taken yet
+    pub fn make_move(&mut self, r#move: Move) -> Result<(), PlayFieldError> {
+        if self.pending_move.is_some() {
+            return Err(PlayFieldError::InvalidPhaseError {
+                message: "The stones to take of the last move must be taken before making another one.",
+            });
+        }
+        let r#move = self.position.resolve_move_kind(r#move);
+        self.position.check_move_kind(&r#move)?;
+
+        if !r#move.takes().is_empty() {
+            return self.apply_move(&r#move);
+        }
+
+        let color = self.position.side_to_move;
+        let mut simulation = self.position.play_field.clone_without_history();
+        simulation.try_set_or_move(&r#move, color)?;
+
+        let mills_closed = simulation.get_mill_crossing(r#move.target_field()).len() / 3;
+        let takes_left = self.get_rules().takes_for_mills(mills_closed);
+
+        if takes_left == 0 || simulation.get_takeable_fields(color).is_empty() {
+            self.apply_move(&r#move)
+        } else {
+            self.pending_move = Some(PendingMove { r#move, takes: Takes::new(), simulation, takes_left });
+            Ok(())
+        }
+    }
+
+    /// Takes the stone on the specified field for the pending move, which is applied after the last stone to take
+    /// was taken or when there's nothing left the player could take.
+    ///
+    /// Handled extreme cases additionally to the ones of [PlayField::try_take]:
+    /// - There is no pending move
+    pub fn take(&mut self, field: Field) -> Result<(), PlayFieldError> {
+        let color = self.position.side_to_move;
+        let Some(pending_move) = self.pending_move.as_mut() else {
+            return Err(PlayFieldError::InvalidPhaseError {
+                message: "There is no closed mill which would permit taking a stone.",
+            });
+        };
+
+        pending_move.simulation.try_take(field, color)?;
+        pending_move.takes.push(field);
+        pending_move.takes_left -= 1;
+
+        if pending_move.takes_left == 0 || pending_move.simulation.get_takeable_fields(color).is_empty() {
+            let pending_move = self.pending_move.take().unwrap();
+            self.apply_move(&pending_move.r#move.with_takes(pending_move.takes))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Ends the game by the resignation of the player with the specified color
+    pub fn resign(&mut self, color: PlayerColor) -> Result<(), PlayFieldError> {
+        self.terminate(GameResult::win(!color, WinReason::Resignation))
+    }
+
+    /// Ends the game by a draw both players agreed on
+    pub fn agree_to_draw(&mut self) -> Result<(), PlayFieldError> {
+        self.terminate(GameResult::Draw(DrawReason::Agreement))
+    }
+
+    /// Ends the game by the player with the specified color running out of time
+    pub fn time_out(&mut self, color: PlayerColor) -> Result<(), PlayFieldError> {
+        self.terminate(GameResult::win(!color, WinReason::Timeout))
+    }
+
+    /// Takes back the pending move, including the stones taken for it so far. If there is none, the last applied move
+    /// is taken back instead, which continues a terminated game.
+    /// Returns the move taken back and the color of the player who made it, if there was a move to take back.
+    pub fn undo(&mut self) -> Option<(Move, PlayerColor)> {
+        if let Some(pending_move) = self.pending_move.take() {
+            return Some((pending_move.r#move, self.position.side_to_move));
+        }
+
+        let undone = self.position.undo()?;
+        self.position_history.pop();
+        self.result = None;
+
+        Some(undone)
+    }
+
+    /// Applies the move taken back last by [Game::undo] again, dropping the pending move.
+    /// Returns the move and the color of the player who made it, if there was a move to redo.
+    pub fn redo(&mut self) -> Option<(Move, PlayerColor)> {
+        if self.result.is_some() {
+            return None;
+        }
+        self.pending_move = None;
+
+        let redone = self.position.redo()?;
+        self.position_history.push(self.position.clone_without_history());
+        self.update_result();
+
+        Some(redone)
+    }
+}
+
+impl Game {
+    fn apply_move(&mut self, r#move: &Move) -> Result<(), PlayFieldError> {
+        self.position.apply_move(r#move)?;
+        self.position_history.push(self.position.clone_without_history());
+        self.update_result();
+
+        Ok(())
+    }
+
+    fn terminate(&mut self, result: GameResult) -> Result<(), PlayFieldError> {
+        if self.result.is_some() {
+            return Err(PlayFieldError::InvalidPhaseError { message: "The game already terminated." });
+        }
+
+        self.pending_move = None;
+        self.result = Some(result);
+        self.position.game_phase = GamePhase::Terminated;
+        Ok(())
+    }
+
+    /// Checks if the game is decided for the side to move, which loses if it has too few stones left or can't move
+    /// any of them, or if one of the draw conditions of the rules is met
+    fn update_result(&mut self) {
+        let color = self.position.side_to_move;
+        let rules = *self.get_rules();
+        let play_field = &self.position.play_field;
+
+        // The stones in the hand are counted as well, as taking stones decides the game in the set phase on the small
+        // boards
+        let stones_in_hand = self.position.get_stones_in_hand(color);
+        let stone_count = play_field.get_amount_of_stones(color) + stones_in_hand;
+
+        let result = if stone_count <= rules.losing_stone_count {
+            Some(GameResult::win(!color, WinReason::TooFewStones))
+        } else if stones_in_hand == 0 && !play_field.can_move(color) {
+            let (stones_white, stones_black) = play_field.amount_of_stones;
+            let board_is_full = (stones_white + stones_black) as usize == rules.board.point_count();
+
+            Some(if board_is_full && rules.draw_conditions.full_board {
+                GameResult::Draw(DrawReason::FullBoard)
+            } else if rules.blocked_loses {
+                GameResult::win(!color, WinReason::NoLegalMoves)
+            } else {
+                GameResult::Draw(DrawReason::Blocked)
+            })
+        } else {
+            self.check_for_draw().map(GameResult::Draw)
+        };
+
+        if let Some(result) = result {
+            self.result = Some(result);
+            self.position.game_phase = GamePhase::Terminated;
+        }
+    }
+
+    /// Checks the draw conditions of the rules, which are a threefold repetition of the current position & too many
+    /// moves without closing a mill
+    fn check_for_draw(&self) -> Option<DrawReason> {
+        let draw_conditions = self.get_rules().draw_conditions;
+
+        let repetitions = self.position_history.iter().filter(|&position| position == &self.position).count();
+        if draw_conditions.threefold_repetition && 3 <= repetitions {
+            return Some(DrawReason::ThreefoldRepetition);
+        }
+
+        match draw_conditions.moves_without_mill {
+            Some(moves) if 2 * moves <= self.position.get_plies_without_mill() => Some(DrawReason::MovesWithoutMill),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor;
+
+    use super::Game;
+    use crate::game::moves::apply_all;
+    use crate::game::position::Position;
+    use crate::game::result::{DrawReason, GameResult, WinReason};
+    use crate::game::rules::{DrawConditions, RuleSet};
+    use crate::game::GamePhase;
+
+    #[test]
+    fn mills_wait_for_the_stones_to_take() {
+        let mut game = Game::default();
+        apply_all(&["a1", "b2", "d1", "f2", "g1"], |r#move| game.make_move(r#move));
+
+        assert_eq!(game.get_pending_takes(), 1);
+        assert!(matches!(game.get_side_to_move(), PlayerColor::White));
+        assert!(game.generate_moves().is_empty());
+        assert!(game.make_move("a7".parse().unwrap()).is_err());
+        assert!(game.take(('A', 1)).is_err());
+
+        game.take(('B', 2)).unwrap();
+        assert_eq!(game.get_pending_takes(), 0);
+        assert!(matches!(game.get_side_to_move(), PlayerColor::Black));
+        assert_eq!(game.get_last_move().unwrap().to_string(), "g1xb2");
+        assert!(game.take(('F', 2)).is_err());
+    }
+
+    #[test]
+    fn undo_takes_back_the_pending_move_only() {
+        let mut game = Game::default();
+        apply_all(&["a1", "b2", "d1", "f2", "g1"], |r#move| game.make_move(r#move));
+
+        let (r#move, color) = game.undo().unwrap();
+        assert_eq!(r#move.to_string(), "g1");
+        assert!(matches!(color, PlayerColor::White));
+        assert!(game.get_pending_move().is_none());
+        assert_eq!(game.get_last_move().unwrap().to_string(), "f2");
+        assert_eq!(game.get_position().get_stones_in_hand(PlayerColor::White), 7);
+    }
+
+    #[test]
+    fn taking_decides_three_mens_morris() {
+        let mut game = Game::new(RuleSet::three_mens_morris());
+        apply_all(&["a1", "b2", "b1", "a2", "c1"], |r#move| game.make_move(r#move));
+        game.take(('B', 2)).unwrap();
+
+        assert_eq!(game.get_result(), Some(GameResult::WhiteWins(WinReason::TooFewStones)));
+        assert_eq!(game.get_phase(), GamePhase::Terminated);
+        assert!(game.make_move("c2".parse().unwrap()).is_err());
+
+        // Taking back the deciding move continues the game
+        game.undo();
+        assert!(game.get_result().is_none());
+        assert_eq!(game.get_phase(), GamePhase::Set);
+    }
+
+    #[test]
+    fn threefold_repetition_draws() {
+        let rules = RuleSet {
+            draw_conditions: DrawConditions { threefold_repetition: true, ..Default::default() },
+            ..RuleSet::three_mens_morris()
+        };
+        let mut game = Game::new(rules);
+        apply_all(&["a1", "b1", "c1", "b2", "b3", "a2"], |r#move| game.make_move(r#move));
+
+        for _ in 0..2 {
+            assert!(game.get_result().is_none());
+            apply_all(&["b3-c3", "a2-a3", "c3-b3", "a3-a2"], |r#move| game.make_move(r#move));
+        }
+        assert_eq!(game.get_result(), Some(GameResult::Draw(DrawReason::ThreefoldRepetition)));
+        // The positions are compared without the moves leading to them, which therefore aren't kept
+        assert!(game.get_position_history().iter().all(|position| position.play_field.get_history().is_empty()));
+    }
+
+    #[test]
+    fn plies_without_mill_are_continued_from_the_notation() {
+        let rules = RuleSet {
+            draw_conditions: DrawConditions { moves_without_mill: Some(50), ..Default::default() },
+            ..Default::default()
+        };
+        let position = Position::from_notation("WWEEEEEEEEEEEEEWBBEEEEEB w m 0 0 99 20", rules).unwrap();
+        let mut game = Game::from_position(position);
+        assert!(game.get_result().is_none());
+
+        game.make_move("e5-e4".parse().unwrap()).unwrap();
+        assert_eq!(game.get_result(), Some(GameResult::Draw(DrawReason::MovesWithoutMill)));
+    }
+
+    #[test]
+    fn resigned_games_take_no_moves() {
+        let mut game = Game::default();
+        game.resign(PlayerColor::White).unwrap();
+
+        assert_eq!(game.get_result(), Some(GameResult::BlackWins(WinReason::Resignation)));
+        assert!(game.agree_to_draw().is_err());
+        assert!(game.make_move("a1".parse().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Contains the [Move] type, which models a complete turn of a player including the stones taken by it.
+//! Moves are parsed from & printed to a compact algebraic notation:
+//! - `d1` places a stone on D1
+//! - `a1-d1` moves a stone from A1 to D1, which is the notation for slides & jumps. It's parsed as a slide, which
+//!   [crate::game::position::Position::apply_move] turns into a jump if the player may jump.
+//! - `a1-d1xg7` additionally takes the opponents stone on G7, `xg7xb2` takes two stones after closing two mills
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use smallvec::SmallVec;
+
+use crate::game::Field;
+
+/// The fields of the stones taken in a turn. Two takes at most happen, which is when two mills are closed at once.
+pub type Takes = SmallVec<[Field; 2]>;
+
+/// A complete turn of a player.
+/// The takes are the fields of the opponents stones which are taken, if the move closes at least one mill.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Move {
+    /// Placing a stone from the hand onto a free field in the set phase
+    Place { field: Field, takes: Takes },
+    /// Moving a stone along a line to the next free field
+    Slide { start_field: Field, target_field: Field, takes: Takes },
+    /// Moving a stone to any free field, which is allowed if a player only has 3 stones left
+    Jump { start_field: Field, target_field: Field, takes: Takes },
+}
+
+impl Move {
+    /// Returns the field a stone is moved from, which doesn't exist for placements
+    pub fn start_field(&self) -> Option<Field> {
+        match self {
+            Move::Place { .. } => None,
+            Move::Slide { start_field, .. } | Move::Jump { start_field, .. } => Some(*start_field),
+        }
+    }
+
+    /// Returns the field a stone is placed or moved on
+    pub fn target_field(&self) -> Field {
+        match self {
+            Move::Place { field, .. } => *field,
+            Move::Slide { target_field, .. } | Move::Jump { target_field, .. } => *target_field,
+        }
+    }
+
+    pub fn takes(&self) -> &[Field] {
+        match self {
+            Move::Place { takes, .. } | Move::Slide { takes, .. } | Move::Jump { takes, .. } => takes,
+        }
+    }
+
+    /// Returns the same move with the specified takes instead of the current ones
+    pub fn with_takes(self, takes: Takes) -> Self {
+        match self {
+            Move::Place { field, .. } => Move::Place { field, takes },
+            Move::Slide { start_field, target_field, .. } => Move::Slide { start_field, target_field, takes },
+            Move::Jump { start_field, target_field, .. } => Move::Jump { start_field, target_field, takes },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MoveParseError {
+    InvalidField { field: String, message: &'static str },
+    InvalidFormat { input: String, message: &'static str },
+}
+
+impl Display for MoveParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveParseError::InvalidField { field, message } => {
+                f.write_fmt(format_args!("Error parsing field \"{field}\" - {message}"))
+            }
+            MoveParseError::InvalidFormat { input, message } => {
+                f.write_fmt(format_args!("Error parsing move \"{input}\" - {message}"))
+            }
+        }
+    }
+}
+
+/// Parses a field in the notation of a column letter between A-G, which might be lower case, followed by a row
+/// number between 1-7. It's not checked if the field actually is a point of the play field.
+pub fn parse_field(input: &str) -> Result<Field, MoveParseError> {
+    let error = |message| Err(MoveParseError::InvalidField { field: input.to_string(), message });
+
+    let mut chars = input.chars();
+    let (Some(column), Some(row), None) = (chars.next(), chars.next(), chars.next()) else {
+        return error("A field must consist of exactly one letter followed by one number.");
+    };
+
+    let column = column.to_ascii_uppercase();
+    if !('A'..='G').contains(&column) {
+        return error("The column letter isn't between A - G.");
+    }
+
+    match row.to_digit(10) {
+        Some(row) if (1..=7).contains(&row) => Ok((column, row as u8)),
+        _ => error("The row number isn't between 1 - 7."),
+    }
+}
+
+/// Formats a field in the lower case notation used for moves, e.g. `d1`
+pub fn format_field(field: Field) -> String {
+    format!("{}{}", field.0.to_ascii_lowercase(), field.1)
+}
+
+/// Parses the moves & applies them one after another using the passed function, panicking on the first one which
+/// can't be parsed or applied. Shared by the tests of everything moves are applied to.
+#[cfg(test)]
+pub fn apply_all<E: fmt::Debug>(notations: &[&str], mut apply: impl FnMut(Move) -> Result<(), E>) {
+    for notation in notations {
+        apply(notation.parse().unwrap()).unwrap();
+    }
+}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(start_field) = self.start_field() {
+            f.write_fmt(format_args!("{}-", format_field(start_field)))?;
+        }
+        f.write_str(&format_field(self.target_field()))?;
+
+        for field_to_take in self.takes() {
+            f.write_fmt(format_args!("x{}", format_field(*field_to_take)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a move in the algebraic notation described in the module documentation.
+/// Moves in the form of `a1-d1` are always parsed as [Move::Slide], because the notation doesn't distinguish jumps.
+/// [crate::game::position::Position::resolve_move_kind] tells them apart by the position they're made in.
+impl FromStr for Move {
+    type Err = MoveParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let mut parts = input.split(['x', 'X']);
+
+        // split always returns at least one element
+        let fields = parts.next().unwrap();
+        let takes = parts.map(parse_field).collect::<Result<Takes, _>>()?;
+
+        if 2 < takes.len() {
+            return Err(MoveParseError::InvalidFormat {
+                input: input.to_string(),
+                message: "At most two stones can be taken, which is when two mills are closed at once.",
+            });
+        }
+
+        match fields.split_once('-') {
+            Some((start_field, target_field)) => Ok(Move::Slide {
+                start_field: parse_field(start_field)?,
+                target_field: parse_field(target_field)?,
+                takes,
+            }),
+            None => Ok(Move::Place { field: parse_field(fields)?, takes }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Move, MoveParseError, Takes};
+
+    #[test]
+    fn parse_and_print_round_trip() {
+        for notation in ["d1", "a1-d1", "a1-d1xg7", "d1xg7xb2"] {
+            assert_eq!(notation.parse::<Move>().unwrap().to_string(), notation);
+        }
+
+        assert_eq!(
+            "A1-D1xG7".parse::<Move>().unwrap(),
+            Move::Slide {
+                start_field: ('A', 1),
+                target_field: ('D', 1),
+                takes: Takes::from_slice(&[('G', 7)])
+            }
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!("h1".parse::<Move>(), Err(MoveParseError::InvalidField { .. })));
+        assert!(matches!("a8".parse::<Move>(), Err(MoveParseError::InvalidField { .. })));
+        assert!(matches!("a1-".parse::<Move>(), Err(MoveParseError::InvalidField { .. })));
+        assert!(matches!("d1xa1xa4xa7".parse::<Move>(), Err(MoveParseError::InvalidFormat { .. })));
+    }
+}
@@ -0,0 +1,164 @@
+//! Contains the [Player] trait, which is the interface between a [Game] & whoever chooses the moves of one side,
+//! e.g. a human at a terminal, a bot or a script replaying a game.
+
+use mill_playfield::PlayerColor;
+
+use crate::game::moves::Move;
+use crate::game::result::GameResult;
+use crate::game::{Field, Game};
+
+pub mod computer;
+pub mod mcts;
+
+/// The action a [Player] chooses on his turn, which either is a move, a command to take back or redo moves, a draw
+/// offer or the resignation
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TurnAction {
+    Move(Move),
+    Undo,
+    Redo,
+    OfferDraw,
+    Resign,
+}
+
+pub trait Player {
+    fn get_name(&self) -> &str;
+
+    /// Chooses the placement or the move of the side to move of the game.
+    /// If the move closes mills without specifying the stones to take, they're chosen by [Player::choose_take].
+    ///
+    /// The action might be rejected by the game, in which case the player is asked again.
+    fn choose_move(&mut self, game: &Game) -> TurnAction;
+
+    /// Chooses a stone of the opponent to take for the pending move of the game.
+    /// The closed mills & the takeable stones are looked up on [Game::get_pending_play_field].
+    fn choose_take(&mut self, game: &Game) -> Field;
+
+    /// Answers the draw offered by the opponent, which is declined by default
+    fn accept_draw(&mut self, _game: &Game) -> bool {
+        false
+    }
+
+    /// If the player chooses his moves on his own, so moves taken back are taken back until it's a human's turn
+    fn is_computer(&self) -> bool {
+        false
+    }
+}
+
+/// The amount of actions in a row a player may choose, which are rejected by [play_game], before he resigns
+const MAX_REJECTED_ACTIONS: u32 = 3;
+
+/// Plays the game between the players without any output until it terminates & returns its result.
+/// The first player plays white, the second one black.
+///
+/// Handled extreme cases:
+/// - The game rejects the chosen move or take, so the player is asked again
+/// - Undo & redo aren't supported, so they're rejected as well
+/// - A draw offer is declined, which also counts as a rejected action to stop the offers from repeating forever
+/// - The player chooses [MAX_REJECTED_ACTIONS] rejected actions in a row, which is treated as resignation
+pub fn play_game(game: &mut Game, players: &mut [Box<dyn Player>; 2]) -> GameResult {
+    let mut rejected_actions = 0;
+
+    loop {
+        if let Some(result) = game.get_result() {
+            break result;
+        }
+
+        let color = game.get_side_to_move();
+        if MAX_REJECTED_ACTIONS <= rejected_actions {
+            game.resign(color).unwrap();
+            continue;
+        }
+
+        let (player_index, opponent_index) = match color {
+            PlayerColor::White => (0, 1),
+            PlayerColor::Black => (1, 0),
+        };
+
+        let accepted = if 0 < game.get_pending_takes() {
+            let field = players[player_index].choose_take(game);
+            game.take(field).is_ok()
+        } else {
+            match players[player_index].choose_move(game) {
+                TurnAction::Move(r#move) => game.make_move(r#move).is_ok(),
+                TurnAction::Undo | TurnAction::Redo => false,
+                TurnAction::OfferDraw if players[opponent_index].accept_draw(game) => {
+                    game.agree_to_draw().unwrap();
+                    true
+                }
+                TurnAction::OfferDraw => false,
+                TurnAction::Resign => {
+                    game.resign(color).unwrap();
+                    true
+                }
+            }
+        };
+
+        rejected_actions = match accepted {
+            true => 0,
+            false => rejected_actions + 1,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor;
+
+    use super::computer::{ComputerPlayer, Strength};
+    use super::{play_game, Player, TurnAction};
+    use crate::game::result::{GameResult, WinReason};
+    use crate::game::rules::{DrawConditions, RuleSet};
+    use crate::game::{Field, Game, GamePhase};
+
+    /// Tries to place its stones on the same field over & over again
+    struct IllegalPlayer;
+
+    impl Player for IllegalPlayer {
+        fn get_name(&self) -> &str {
+            "Illegal"
+        }
+
+        fn choose_move(&mut self, _game: &Game) -> TurnAction {
+            TurnAction::Move("b2-b7".parse().unwrap())
+        }
+
+        fn choose_take(&mut self, _game: &Game) -> Field {
+            ('B', 7)
+        }
+    }
+
+    #[test]
+    fn computers_play_until_the_game_terminates() {
+        let rules = RuleSet {
+            draw_conditions: DrawConditions {
+                moves_without_mill: Some(30),
+                threefold_repetition: true,
+                full_board: false,
+            },
+            ..RuleSet::three_mens_morris()
+        };
+        let mut game = Game::new(rules);
+        let mut players: [Box<dyn Player>; 2] = [
+            Box::new(ComputerPlayer::with_seed("White", Strength::Beginner, 1)),
+            Box::new(ComputerPlayer::with_seed("Black", Strength::Intermediate, 2)),
+        ];
+
+        let result = play_game(&mut game, &mut players);
+        assert_eq!(game.get_result(), Some(result));
+        assert_eq!(game.get_phase(), GamePhase::Terminated);
+    }
+
+    #[test]
+    fn illegal_moves_resign() {
+        let mut game = Game::default();
+        let mut players: [Box<dyn Player>; 2] =
+            [Box::new(ComputerPlayer::with_seed("White", Strength::Beginner, 1)), Box::new(IllegalPlayer)];
+
+        let result = play_game(&mut game, &mut players);
+        assert_eq!(result, GameResult::WhiteWins(WinReason::Resignation));
+        // Black resigned after the first move of white
+        assert_eq!(game.get_position().get_stones_in_hand(PlayerColor::White), 8);
+        assert_eq!(game.get_position().get_stones_in_hand(PlayerColor::Black), 9);
+    }
+}
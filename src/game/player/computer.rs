@@ -0,0 +1,277 @@
+//! Contains the [ComputerPlayer], which chooses its moves by searching the moves of the [Position] a few turns ahead.
+//! The [Strength] determines how many turns it looks ahead.
+
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use mill_playfield::PlayerColor;
+use nanorand::{Rng, WyRand};
+
+use super::{Player, TurnAction};
+use crate::game::engine::evaluation::Weights;
+use crate::game::engine::search::{SearchLimit, Searcher};
+use crate::game::engine::EnginePosition;
+use crate::game::moves::Move;
+use crate::game::position::Position;
+use crate::game::{Field, Game};
+
+/// The score of a won position, which is reduced by the turns it takes to win
+const WIN_SCORE: i32 = 10_000;
+/// The score of every stone a player has more than his opponent, which outweighs the mobility
+const STONE_WEIGHT: i32 = 100;
+/// The time the [Strength::Expert] searches for its move
+const EXPERT_SEARCH_TIME: Duration = Duration::from_secs(1);
+
+/// The strength of the [ComputerPlayer], which is the amount of turns it looks ahead
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Strength {
+    /// Only looks at the outcome of its own move, which misses the mills of the opponent
+    Beginner,
+    /// Also looks at the reply of the opponent
+    Intermediate,
+    /// Looks three turns ahead
+    Advanced,
+    /// Searches as deep as it gets in a second using the [crate::game::engine], which only supports the board of
+    /// Nine Men's Morris. Plays like the advanced computer on the other boards.
+    Expert,
+}
+
+impl Strength {
+    pub fn get_search_depth(&self) -> u32 {
+        match self {
+            Strength::Beginner => 1,
+            Strength::Intermediate => 2,
+            Strength::Advanced | Strength::Expert => 3,
+        }
+    }
+}
+
+impl Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Strength::Beginner => "Beginner",
+            Strength::Intermediate => "Intermediate",
+            Strength::Advanced => "Advanced",
+            Strength::Expert => "Expert",
+        })
+    }
+}
+
+pub struct ComputerPlayer {
+    name: String,
+    strength: Strength,
+    // Chooses between the moves with the best score, so the computer doesn't play the same game every time
+    rng: WyRand,
+    // Only the expert searches with the engine, whose transposition table is kept from one move to the next
+    searcher: Option<Searcher>,
+}
+
+impl ComputerPlayer {
+    pub fn new(name: &str, strength: Strength) -> Self {
+        ComputerPlayer {
+            name: name.to_string(),
+            strength,
+            rng: WyRand::new(),
+            searcher: Self::create_searcher(strength),
+        }
+    }
+
+    /// Returns a computer player which chooses the same moves in the same positions every time it's created with the
+    /// same seed
+    pub fn with_seed(name: &str, strength: Strength, seed: u64) -> Self {
+        ComputerPlayer {
+            name: name.to_string(),
+            strength,
+            rng: WyRand::new_seed(seed),
+            searcher: Self::create_searcher(strength),
+        }
+    }
+
+    fn create_searcher(strength: Strength) -> Option<Searcher> {
+        match strength {
+            Strength::Expert => Some(Searcher::new()),
+            _ => None,
+        }
+    }
+
+    pub fn get_strength(&self) -> Strength {
+        self.strength
+    }
+
+    /// Evaluates the positions searched by the engine by the weights, which only the [Strength::Expert] does
+    pub fn set_weights(&mut self, weights: Weights) {
+        if let Some(searcher) = &mut self.searcher {
+            searcher.set_weights(weights);
+        }
+    }
+
+    /// Returns one of the moves with the best score for the side to move of the position, which includes the stones to
+    /// take. The history are the positions of the game before the position, which the expert counts for the threefold
+    /// repetitions. Returns nothing if there's no legal move.
+    pub fn search_best_move(&mut self, position: &Position, history: &[Position]) -> Option<Move> {
+        if let (Some(searcher), Ok(engine_position)) = (&mut self.searcher, EnginePosition::try_from(position)) {
+            let history = history.iter().map(EnginePosition::try_from).collect::<Result<Vec<_>, _>>();
+            searcher.set_history(&history.expect("The positions of the game are on the same board."));
+            let result = searcher.search(&engine_position, SearchLimit::Time(EXPERT_SEARCH_TIME));
+            return result.map(|result| result.best_move);
+        }
+
+        // The history isn't needed for searching & would be copied on every move applied
+        let root = position.clone_without_history();
+        let depth = self.strength.get_search_depth();
+
+        let scored_moves = root
+            .generate_moves()
+            .into_iter()
+            .map(|r#move| {
+                let score = -Self::search(&Self::apply(&root, &r#move), depth - 1);
+                (r#move, score)
+            })
+            .collect::<Vec<_>>();
+
+        let best_score = scored_moves.iter().map(|(_, score)| *score).max()?;
+        let mut best_moves = scored_moves.into_iter().filter(|(_, score)| *score == best_score).collect::<Vec<_>>();
+
+        let index = self.rng.generate_range(0..best_moves.len());
+        Some(best_moves.swap_remove(index).0)
+    }
+
+    /// Returns the score of the position from the view of the side to move, looking the specified amount of turns ahead
+    fn search(position: &Position, depth: u32) -> i32 {
+        let color = position.side_to_move;
+        let rules = position.get_rules();
+
+        // Losing sooner is worse than losing later, which is when less turns are left to search
+        let loss_score = -WIN_SCORE - depth as i32;
+        if Self::count_stones(position, color) <= rules.losing_stone_count {
+            return loss_score;
+        }
+
+        let moves = position.generate_moves();
+        if moves.is_empty() {
+            return match rules.blocked_loses && position.get_stones_in_hand(color) == 0 {
+                true => loss_score,
+                false => 0,
+            };
+        }
+        if depth == 0 {
+            return Self::evaluate(position);
+        }
+
+        moves.iter().map(|r#move| -Self::search(&Self::apply(position, r#move), depth - 1)).max().unwrap()
+    }
+
+    /// Rates the position from the view of the side to move by the difference of stones & of the moves possible
+    fn evaluate(position: &Position) -> i32 {
+        let color = position.side_to_move;
+        let mobility = |color: PlayerColor| {
+            let stones_in_hand = position.get_stones_in_hand(color);
+            position.play_field.generate_moves(color, stones_in_hand).len() as i32
+        };
+
+        let stone_difference = Self::count_stones(position, color) as i32 - Self::count_stones(position, !color) as i32;
+        STONE_WEIGHT * stone_difference + mobility(color) - mobility(!color)
+    }
+
+    /// Returns the amount of stones the player has left on the play field & in his hand
+    fn count_stones(position: &Position, color: PlayerColor) -> u32 {
+        position.play_field.get_amount_of_stones(color) + position.get_stones_in_hand(color)
+    }
+
+    fn apply(position: &Position, r#move: &Move) -> Position {
+        let mut position = position.clone();
+        position.apply_move(r#move).expect("Generated moves must be legal.");
+        position
+    }
+}
+
+impl Player for ComputerPlayer {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Chooses the best move found including the stones to take, or resigns if there's no legal move left
+    fn choose_move(&mut self, game: &Game) -> TurnAction {
+        // The last position of the history is the current one
+        let history = game.get_position_history().split_last().map_or(&[][..], |(_, history)| history);
+        match self.search_best_move(game.get_position(), history) {
+            Some(r#move) => TurnAction::Move(r#move),
+            None => TurnAction::Resign,
+        }
+    }
+
+    /// Chooses the stone whose taking leaves the opponent with the least moves. This is only needed if the move was
+    /// made without the stones to take, as the moves chosen by the computer include them.
+    fn choose_take(&mut self, game: &Game) -> Field {
+        let play_field = game.get_pending_play_field().expect("Stones are only taken for a pending move.");
+        let color = game.get_side_to_move();
+        let opponents_stones_in_hand = game.get_position().get_stones_in_hand(!color);
+
+        let opponents_moves_after_taking = |&field: &Field| {
+            let mut play_field = play_field.clone_without_history();
+            play_field.try_take(field, color).expect("Takeable fields must be takeable.");
+            play_field.generate_moves(!color, opponents_stones_in_hand).len()
+        };
+
+        let takeable_fields = play_field.get_takeable_fields(color);
+        takeable_fields
+            .into_iter()
+            .min_by_key(opponents_moves_after_taking)
+            .expect("A pending move has stones to take.")
+    }
+
+    fn is_computer(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComputerPlayer, Strength};
+    use crate::game::moves::apply_all;
+    use crate::game::position::Position;
+    use crate::game::rules::RuleSet;
+
+    fn position_after(notations: &[&str]) -> Position {
+        position_with_rules_after(RuleSet::default(), notations)
+    }
+
+    fn position_with_rules_after(rules: RuleSet, notations: &[&str]) -> Position {
+        let mut position = Position::new(rules);
+        apply_all(notations, |r#move| position.apply_move(&r#move));
+        position
+    }
+
+    #[test]
+    fn closes_mills() {
+        let position = position_after(&["a1", "g7", "d1", "g4"]);
+        let mut computer = ComputerPlayer::with_seed("Computer", Strength::Beginner, 1);
+
+        let r#move = computer.search_best_move(&position, &[]).unwrap();
+        assert_eq!(r#move.target_field(), ('G', 1));
+        assert_eq!(r#move.takes().len(), 1);
+    }
+
+    #[test]
+    fn prevents_mills_of_the_opponent() {
+        let position = position_after(&["a1", "g7", "d1"]);
+        let mut computer = ComputerPlayer::with_seed("Computer", Strength::Intermediate, 1);
+
+        assert_eq!(computer.search_best_move(&position, &[]).unwrap().to_string(), "g1");
+    }
+
+    #[test]
+    fn solves_three_mens_morris() {
+        // The expert can't use the engine on the small board & searches three turns ahead instead
+        let mut computer = ComputerPlayer::with_seed("Computer", Strength::Expert, 1);
+
+        // Black loses unless he blocks the mill on B1, as he's left with two stones after a take
+        let position = position_with_rules_after(RuleSet::three_mens_morris(), &["a1", "b2", "c1"]);
+        assert_eq!(computer.search_best_move(&position, &[]).unwrap().to_string(), "b1");
+
+        let position = position_with_rules_after(RuleSet::three_mens_morris(), &["a1", "b2", "c1", "a3"]);
+        let r#move = computer.search_best_move(&position, &[]).unwrap();
+        assert_eq!(r#move.target_field(), ('B', 1));
+        assert_eq!(r#move.takes().len(), 1);
+    }
+}
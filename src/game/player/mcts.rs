@@ -0,0 +1,116 @@
+//! Contains the [MctsPlayer], which chooses its moves by a [MonteCarloTreeSearch].
+//! It only plays on the board of Nine Men's Morris, on the other boards it plays like the advanced [ComputerPlayer].
+
+use nanorand::{Rng, WyRand};
+
+use super::computer::{ComputerPlayer, Strength};
+use super::{Player, TurnAction};
+use crate::game::engine::mcts::{MctsConfig, MonteCarloTreeSearch};
+use crate::game::engine::EnginePosition;
+use crate::game::{Field, Game};
+
+pub struct MctsPlayer {
+    name: String,
+    search: MonteCarloTreeSearch,
+    // Chooses the moves on the boards the search doesn't support
+    fallback: ComputerPlayer,
+    // Chooses the stones to take
+    rng: WyRand,
+}
+
+impl MctsPlayer {
+    pub fn new(name: &str, config: MctsConfig) -> Self {
+        MctsPlayer {
+            name: name.to_string(),
+            search: MonteCarloTreeSearch::new(config),
+            fallback: ComputerPlayer::new(name, Strength::Advanced),
+            rng: WyRand::new(),
+        }
+    }
+
+    /// Returns a player which chooses the same moves in the same positions every time it's created with the same
+    /// seed, as long as the budget of the config is a number of iterations
+    pub fn with_seed(name: &str, config: MctsConfig, seed: u64) -> Self {
+        MctsPlayer {
+            name: name.to_string(),
+            search: MonteCarloTreeSearch::with_seed(config, seed),
+            fallback: ComputerPlayer::with_seed(name, Strength::Advanced, seed),
+            rng: WyRand::new_seed(seed),
+        }
+    }
+}
+
+impl Player for MctsPlayer {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Chooses the move visited most often by the search including the stones to take, or resigns if there's no
+    /// legal move left. On the boards the search doesn't support, the move of the advanced [ComputerPlayer] is chosen.
+    fn choose_move(&mut self, game: &Game) -> TurnAction {
+        let position = game.get_position();
+
+        let r#move = match EnginePosition::try_from(position) {
+            Ok(engine_position) => self.search.search(&engine_position).map(|result| result.best_move),
+            Err(_) => return self.fallback.choose_move(game),
+        };
+
+        match r#move {
+            Some(r#move) => TurnAction::Move(r#move),
+            None => TurnAction::Resign,
+        }
+    }
+
+    /// Chooses a random stone, which is only needed if the move was made without the stones to take, as the moves
+    /// chosen by the search include them
+    fn choose_take(&mut self, game: &Game) -> Field {
+        let play_field = game.get_pending_play_field().expect("Stones are only taken for a pending move.");
+        let takeable_fields = play_field.get_takeable_fields(game.get_side_to_move());
+
+        takeable_fields[self.rng.generate_range(0..takeable_fields.len())]
+    }
+
+    fn is_computer(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MctsPlayer;
+    use crate::game::engine::mcts::{MctsBudget, MctsConfig};
+    use crate::game::player::computer::{ComputerPlayer, Strength};
+    use crate::game::player::TurnAction;
+    use crate::game::player::{play_game, Player};
+    use crate::game::rules::RuleSet;
+    use crate::game::Game;
+
+    #[test]
+    fn plays_against_the_computer() {
+        let mut game = Game::new(RuleSet::tournament());
+        let config = MctsConfig {
+            budget: MctsBudget::Iterations(10),
+            max_playout_plies: 20,
+            ..Default::default()
+        };
+        let mut players: [Box<dyn Player>; 2] = [
+            Box::new(MctsPlayer::with_seed("Monte Carlo", config, 1)),
+            Box::new(ComputerPlayer::with_seed("Computer", Strength::Beginner, 2)),
+        ];
+
+        let result = play_game(&mut game, &mut players);
+        assert_eq!(game.get_result(), Some(result));
+    }
+
+    #[test]
+    fn plays_like_the_advanced_computer_on_other_boards() {
+        let mut game = Game::new(RuleSet::twelve_mens_morris());
+        game.make_move("a1".parse().unwrap()).unwrap();
+
+        let mut player = MctsPlayer::with_seed("Monte Carlo", MctsConfig::default(), 1);
+        let mut computer = ComputerPlayer::with_seed("Computer", Strength::Advanced, 1);
+        let action = player.choose_move(&game);
+        assert!(matches!(action, TurnAction::Move(_)));
+        assert_eq!(action, computer.choose_move(&game));
+    }
+}
@@ -0,0 +1,343 @@
+//! Contains the [Position] type, which holds everything needed to continue a game from a certain point on:
+//! The [PlayField], the player to move, the stones left in the players hands & the [GamePhase].
+
+use std::hash::{Hash, Hasher};
+
+use mill_playfield::PlayerColor;
+
+use crate::game::moves::Move;
+use crate::game::rules::RuleSet;
+use crate::game::state::{PlayField, PlayFieldError};
+use crate::game::GamePhase;
+
+pub mod notation;
+
+#[derive(Clone)]
+pub struct Position {
+    pub play_field: PlayField,
+    pub side_to_move: PlayerColor,
+    // first one: white, second one: black
+    pub stones_in_hand: (u32, u32),
+    pub game_phase: GamePhase,
+    // Starts with 1 & increases with every move made
+    pub round: u32,
+    // The plies made in the move phase since the last closed mill or placement
+    pub plies_without_mill: u32,
+    // The counts before the moves which reset it to 0, restored when those moves are taken back
+    plies_before_resets: Vec<u32>,
+}
+
+/// The starting position of the set phase with white to move under the default [RuleSet]
+impl Default for Position {
+    fn default() -> Self {
+        Position::new(RuleSet::default())
+    }
+}
+
+/// Two [Position]s are equal if the stones on the [PlayField] & in the hands, the side to move and the phase are.
+/// The round & the plies without mill aren't compared, so a position reached again later on still is the same position.
+impl PartialEq for Position {
+    fn eq(&self, other: &Self) -> bool {
+        self.play_field == other.play_field
+            && matches!(self.side_to_move, PlayerColor::White) == matches!(other.side_to_move, PlayerColor::White)
+            && self.stones_in_hand == other.stones_in_hand
+            && self.game_phase == other.game_phase
+    }
+}
+
+impl Eq for Position {}
+
+impl Hash for Position {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.play_field.hash(state);
+        matches!(self.side_to_move, PlayerColor::White).hash(state);
+        self.stones_in_hand.hash(state);
+        self.game_phase.hash(state);
+    }
+}
+
+impl Position {
+    /// Returns the starting position of the set phase with white to move, on which the specified rules apply
+    pub fn new(rules: RuleSet) -> Self {
+        Self {
+            play_field: PlayField::with_rules(rules),
+            side_to_move: PlayerColor::White,
+            stones_in_hand: (rules.stones_per_player, rules.stones_per_player),
+            game_phase: GamePhase::Set,
+            round: 1,
+            plies_without_mill: 0,
+            plies_before_resets: Vec::new(),
+        }
+    }
+
+    pub fn get_rules(&self) -> &RuleSet {
+        self.play_field.get_rules()
+    }
+
+    /// Returns the amount of stones the player of the specified color has left to place
+    pub fn get_stones_in_hand(&self, color: PlayerColor) -> u32 {
+        match color {
+            PlayerColor::White => self.stones_in_hand.0,
+            PlayerColor::Black => self.stones_in_hand.1,
+        }
+    }
+
+    /// Returns the amount of plies, which are the moves of either player, made in the move phase since the last closed
+    /// mill. Closing a mill resets the count even if there was no stone to take, placements reset it as well.
+    pub fn get_plies_without_mill(&self) -> u32 {
+        self.plies_without_mill
+    }
+
+    /// Returns a copy of the position without the history of its [PlayField], which is enough to compare positions &
+    /// cheaper to keep around
+    pub(crate) fn clone_without_history(&self) -> Position {
+        Position {
+            play_field: self.play_field.clone_without_history(),
+            side_to_move: self.side_to_move,
+            stones_in_hand: self.stones_in_hand,
+            game_phase: self.game_phase,
+            round: self.round,
+            plies_without_mill: self.plies_without_mill,
+            plies_before_resets: Vec::new(),
+        }
+    }
+
+    /// Returns every legal [Move] of the player to move
+    pub fn generate_moves(&self) -> Vec<Move> {
+        self.play_field.generate_moves(self.side_to_move, self.get_stones_in_hand(self.side_to_move))
+    }
+
+    /// Applies the [Move] of the player to move atomically, hands the turn over to the opponent & updates the
+    /// [GamePhase], which switches to the move phase when both players placed all of their stones.
+    ///
+    /// The kind of the move is resolved by [Position::resolve_move_kind] first, so moves parsed from the notation are
+    /// recorded as the jumps they are.
+    ///
+    /// Handled extreme cases additionally to the ones of [PlayField::apply_move] are the ones of [Position::check_move_kind].
+    pub fn apply_move(&mut self, r#move: &Move) -> Result<(), PlayFieldError> {
+        let r#move = &self.resolve_move_kind(r#move.clone());
+        self.check_move_kind(r#move)?;
+
+        let in_move_phase = self.stones_in_hand == (0, 0);
+        self.play_field.apply_move(r#move, self.side_to_move)?;
+        self.count_ply_without_mill(r#move, in_move_phase);
+
+        if let Move::Place { .. } = r#move {
+            *self.get_stones_in_hand_mut(self.side_to_move) -= 1;
+        }
+        self.side_to_move = !self.side_to_move;
+        self.round += 1;
+        self.update_game_phase();
+
+        Ok(())
+    }
+
+    /// Returns the move as the kind the notation can't tell apart: A slide of a player who may jump is a [Move::Jump],
+    /// just as the moves generated for him are. Every other move is returned as it is.
+    pub fn resolve_move_kind(&self, r#move: Move) -> Move {
+        let color = self.side_to_move;
+        let can_jump = self.get_stones_in_hand(color) == 0
+            && self.get_rules().can_fly(self.play_field.get_amount_of_stones(color));
+
+        match r#move {
+            Move::Slide { start_field, target_field, takes } if can_jump => {
+                Move::Jump { start_field, target_field, takes }
+            }
+            r#move => r#move,
+        }
+    }
+
+    /// Checks if the kind of the [Move] is permitted for the player to move in the current phase, without looking at
+    /// the fields of the move.
+    ///
+    /// Handled extreme cases:
+    /// - The game hasn't started yet or is terminated
+    /// - A stone is moved while the player has stones left in his hand, unless the rules permit it
+    /// - A stone is placed while the player has no stones left in his hand
+    /// - A stone jumps while the player has stones left in his hand
+    pub fn check_move_kind(&self, r#move: &Move) -> Result<(), PlayFieldError> {
+        if let GamePhase::Start | GamePhase::Terminated = self.game_phase {
+            return Err(PlayFieldError::InvalidPhaseError {
+                message: "Moves can't be made before the game started or after it terminated.",
+            });
+        }
+
+        let has_stones_in_hand = 0 < self.get_stones_in_hand(self.side_to_move);
+        let rules = self.get_rules();
+
+        let error_message = match *r#move {
+            Move::Place { .. } if !has_stones_in_hand => {
+                Some("Stones can only be placed while the player has stones left in his hand.")
+            }
+            Move::Place { .. } => None,
+            _ if has_stones_in_hand && !rules.moving_in_set_phase => {
+                Some("Stones must be placed while the player has stones left in his hand.")
+            }
+            Move::Jump { .. } if has_stones_in_hand => {
+                Some("Stones can't jump while the player has stones left in his hand.")
+            }
+            Move::Slide { start_field, target_field, .. } if has_stones_in_hand => {
+                match (rules.board.index_of(start_field), rules.board.index_of(target_field)) {
+                    (Some(start_index), Some(target_index))
+                        if !rules.board.are_neighbors(start_index, target_index) =>
+                    {
+                        Some("Stones can only slide while the player has stones left in his hand.")
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match error_message {
+            Some(message) => Err(PlayFieldError::InvalidPhaseError { message }),
+            None => Ok(()),
+        }
+    }
+
+    /// Takes back the last move of the [PlayField], giving placed stones back into the hand of their player.
+    /// Returns the move taken back and the color of the player who made it, if there was a move to take back.
+    pub fn undo(&mut self) -> Option<(Move, PlayerColor)> {
+        let (r#move, color) = self.play_field.undo()?;
+
+        if let Move::Place { .. } = r#move {
+            *self.get_stones_in_hand_mut(color) += 1;
+        }
+        // Counted moves left a count of at least 1, so a count of 0 was reset by the move taken back
+        self.plies_without_mill = match self.plies_without_mill {
+            0 => self.plies_before_resets.pop().unwrap_or(0),
+            plies => plies - 1,
+        };
+        self.side_to_move = color;
+        self.round -= 1;
+        self.update_game_phase();
+
+        Some((r#move, color))
+    }
+
+    /// Applies the move taken back last by [undo] again.
+    /// Returns the move and the color of the player who made it, if there was a move to redo.
+    pub fn redo(&mut self) -> Option<(Move, PlayerColor)> {
+        let in_move_phase = self.stones_in_hand == (0, 0);
+        let (r#move, color) = self.play_field.redo()?;
+        self.count_ply_without_mill(&r#move, in_move_phase);
+
+        if let Move::Place { .. } = r#move {
+            *self.get_stones_in_hand_mut(color) -= 1;
+        }
+        self.side_to_move = !color;
+        self.round += 1;
+        self.update_game_phase();
+
+        Some((r#move, color))
+    }
+
+    /// Counts the move applied last on the [PlayField] if it was made in the move phase & didn't close a mill, which
+    /// otherwise resets the count
+    fn count_ply_without_mill(&mut self, r#move: &Move, in_move_phase: bool) {
+        if in_move_phase && self.play_field.get_mill_crossing(r#move.target_field()).is_empty() {
+            self.plies_without_mill += 1;
+        } else {
+            self.plies_before_resets.push(self.plies_without_mill);
+            self.plies_without_mill = 0;
+        }
+    }
+
+    /// The set phase lasts as long as one of the players has stones left in his hand.
+    /// Taking back a move of a terminated game therefore continues it.
+    fn update_game_phase(&mut self) {
+        self.game_phase = match self.stones_in_hand {
+            (0, 0) => GamePhase::MoveAndJump,
+            _ => GamePhase::Set,
+        };
+    }
+
+    fn get_stones_in_hand_mut(&mut self, color: PlayerColor) -> &mut u32 {
+        match color {
+            PlayerColor::White => &mut self.stones_in_hand.0,
+            PlayerColor::Black => &mut self.stones_in_hand.1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+    use crate::game::moves::{apply_all, Move, Takes};
+    use crate::game::rules::{RuleSet, TakingFromMills};
+
+    #[test]
+    fn stones_are_placed_in_the_set_phase() {
+        let mut position = Position::default();
+        apply_all(&["a1", "g7"], |r#move| position.apply_move(&r#move));
+
+        assert!(position.apply_move(&"a1-a4".parse().unwrap()).is_err());
+        assert_eq!(position.stones_in_hand, (8, 8));
+    }
+
+    #[test]
+    fn lasker_allows_moving_in_the_set_phase() {
+        let mut position = Position::new(RuleSet::lasker());
+        apply_all(&["a1", "g7", "a1-a4", "g7-d7"], |r#move| position.apply_move(&r#move));
+        assert_eq!(position.stones_in_hand, (9, 9));
+
+        // With 1 stone on the play field, but stones in the hand, jumping isn't possible
+        assert!(position.apply_move(&"a4-g1".parse().unwrap()).is_err());
+        let moves = position.generate_moves();
+        assert!(moves.iter().all(|r#move| !matches!(r#move, Move::Jump { .. })));
+        // Placements on the 22 free fields & the slides to A1, A7 & B4
+        assert_eq!(moves.len(), 22 + 3);
+
+        position.undo();
+        assert_eq!(position.stones_in_hand, (9, 9));
+        assert_eq!(position.round, 4);
+    }
+
+    #[test]
+    fn jumps_are_resolved_from_their_notation() {
+        // White has 3 stones left & jumps from A1 into the mill on C5
+        let mut position: Position = "WWEEEEEEEEEEEEEEBBBEEWEE w m 0 0 0 30".parse().unwrap();
+        let jump = Move::Jump {
+            start_field: ('A', 1),
+            target_field: ('C', 5),
+            takes: Takes::from_slice(&[('D', 7)]),
+        };
+
+        let parsed_jump = jump.to_string().parse::<Move>().unwrap();
+        assert_eq!(position.resolve_move_kind(parsed_jump.clone()), jump);
+        position.apply_move(&parsed_jump).unwrap();
+        assert_eq!(position.play_field.get_history().last().unwrap().0, jump);
+
+        // Black has 3 stones as well, but can't jump without flying
+        let mut position =
+            Position::from_notation("WWEEEEEEEEEEEEEEBBBEEWEE b m 0 0 0 30", RuleSet::without_flying()).unwrap();
+        let slide = "g4-g1".parse::<Move>().unwrap();
+        assert_eq!(position.resolve_move_kind(slide.clone()), slide);
+        assert!(position.apply_move(&"g4-a4".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn plies_without_mill_are_counted_in_the_move_phase() {
+        let mut position = Position::default();
+        apply_all(&["a1", "g7", "d1", "a7", "g1xa7"], |r#move| position.apply_move(&r#move));
+        assert_eq!(position.get_plies_without_mill(), 0);
+
+        // White closes the mill on C5, D5 & E5, but can't take as the black stones are protected by their mill
+        let rules = RuleSet {
+            taking_from_mills: TakingFromMills::Never,
+            ..Default::default()
+        };
+        let mut position = Position::from_notation("WWEEEEEEEEEEEEEWBBEEEEEB w m 0 0 7 20", rules).unwrap();
+        apply_all(&["b6-c5", "g7-g4", "c5-b6"], |r#move| position.apply_move(&r#move));
+        assert_eq!(position.get_plies_without_mill(), 2);
+        assert!(position.to_string().ends_with(" 2 23"));
+
+        position.undo();
+        position.undo();
+        assert_eq!(position.get_plies_without_mill(), 0);
+        // Taking back the mill restores the count loaded from the notation
+        position.undo();
+        assert_eq!(position.get_plies_without_mill(), 7);
+        position.redo();
+        assert_eq!(position.get_plies_without_mill(), 0);
+    }
+}
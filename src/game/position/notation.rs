@@ -0,0 +1,277 @@
+//! Contains a FEN like text notation for [Position]s, which extends the 24 character `B/W/E` board string of the
+//! `input_felder` files by the side to move, the phase, the stones in the hands, the plies without mill & the round,
+//! e.g. `EEEEWEEEEEEEEEEBEEEEEEEE b s 8 9 0 2`
+//!
+//! The fields are separated by whitespace:
+//! 1. The board in the field order of `EfficientPlayField::from_coded`, ring by ring from the inner to the outer one.
+//!    Boards of less than 24 points are written in the order of their points, row by row from the bottom
+//! 2. The side to move, `w` or `b`
+//! 3. The phase, `s` for the set phase, `m` for the move phase & `t` for a terminated game
+//! 4. The stones white & black have left in their hands
+//! 5. The plies made in the move phase since the last closed mill, see [Position::get_plies_without_mill]
+//! 6. The round, starting with 1
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use mill_playfield::PlayerColor;
+
+use super::Position;
+use crate::game::board::Board;
+use crate::game::rules::RuleSet;
+use crate::game::state::conversion::FIELD_POS_LUT;
+use crate::game::state::representation::constants::FIELD_COUNT;
+use crate::game::state::representation::types::FieldState;
+use crate::game::state::PlayField;
+use crate::game::{Field, GamePhase};
+
+/// The amount of whitespace separated parts of the notation
+const PART_COUNT: usize = 7;
+
+#[derive(Debug, PartialEq)]
+pub struct PositionParseError {
+    pub input: String,
+    // Starting with 1, pointing at the first character which couldn't be parsed
+    pub column: usize,
+    pub message: &'static str,
+}
+
+/// Prints the message followed by the input & a marker under the offending column
+impl Display for PositionParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "Error parsing position at column {} - {}\n{}\n{:>column$}",
+            self.column,
+            self.message,
+            self.input,
+            '^',
+            column = self.column
+        ))
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for field in notation_order(self.get_rules().board) {
+            let field_state = match self.play_field.get_status_of(field).unwrap() {
+                FieldState::Free => 'E',
+                FieldState::White => 'W',
+                FieldState::Black => 'B',
+            };
+            f.write_fmt(format_args!("{field_state}"))?;
+        }
+
+        let side_to_move = match self.side_to_move {
+            PlayerColor::White => 'w',
+            PlayerColor::Black => 'b',
+        };
+        let game_phase = match self.game_phase {
+            GamePhase::Start | GamePhase::Set => 's',
+            GamePhase::MoveAndJump => 'm',
+            GamePhase::Terminated => 't',
+        };
+
+        f.write_fmt(format_args!(
+            " {side_to_move} {game_phase} {} {} {} {}",
+            self.stones_in_hand.0, self.stones_in_hand.1, self.plies_without_mill, self.round
+        ))
+    }
+}
+
+/// Parses a position in the notation described in the module documentation, on which the default [RuleSet] applies
+impl FromStr for Position {
+    type Err = PositionParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Position::from_notation(input, RuleSet::default())
+    }
+}
+
+impl Position {
+    /// Parses a position in the notation described in the module documentation, on which the specified rules apply.
+    ///
+    /// Handled extreme cases:
+    /// - Fields are missing or there are too many of them
+    /// - The board doesn't consist of one field for every point or contains other characters than `B`, `W` & `E`
+    /// - A player has more stones on the board & in his hand than he got at the start of the game
+    /// - The phase doesn't fit the stones in the hands, e.g. the move phase with stones left to place
+    /// - Plies without mill are given although a player has stones left to place
+    /// - The round isn't a number greater than 0
+    pub fn from_notation(input: &str, rules: RuleSet) -> Result<Self, PositionParseError> {
+        let error = |index: usize, message| PositionParseError { input: input.to_string(), column: index + 1, message };
+
+        let tokens = split_with_indices(input);
+        if tokens.len() < PART_COUNT {
+            return Err(error(
+                input.trim_end().len(),
+                "Missing fields, a position consists of the board, side to move, phase, stones in hand, plies & round.",
+            ));
+        } else if PART_COUNT < tokens.len() {
+            return Err(error(tokens[PART_COUNT].0, "Unexpected field after the round."));
+        }
+
+        let (board_index, board) = tokens[0];
+        let mut play_field = PlayField::with_rules(rules);
+
+        for ((char_index, state), field) in board.char_indices().zip(notation_order(rules.board)) {
+            let color = match state {
+                'E' => continue,
+                'W' => PlayerColor::White,
+                'B' => PlayerColor::Black,
+                _ => return Err(error(board_index + char_index, "The fields of the board must be either B, W or E.")),
+            };
+            if play_field.get_amount_of_stones(color) == rules.stones_per_player {
+                return Err(error(
+                    board_index + char_index,
+                    "A player can't have more stones on the board than he got.",
+                ));
+            }
+            play_field.try_set(field, color).unwrap();
+        }
+
+        let point_count = rules.board.point_count();
+        if board.chars().count() != point_count {
+            let index = match board.char_indices().nth(point_count) {
+                Some((char_index, _)) => board_index + char_index,
+                None => board_index,
+            };
+            return Err(error(index, "The board must consist of exactly one field for every point."));
+        }
+
+        let side_to_move = match tokens[1].1 {
+            "w" | "W" => PlayerColor::White,
+            "b" | "B" => PlayerColor::Black,
+            _ => return Err(error(tokens[1].0, "The side to move must be either w or b.")),
+        };
+
+        let mut stones_in_hand = (0, 0);
+        for (color, (index, token)) in [(PlayerColor::White, tokens[3]), (PlayerColor::Black, tokens[4])] {
+            let Ok(amount) = token.parse::<u32>() else {
+                return Err(error(index, "The stones in hand must be a number."));
+            };
+            if rules.stones_per_player < play_field.get_amount_of_stones(color) + amount {
+                return Err(error(index, "The player has more stones on the board & in his hand than he got."));
+            }
+            match color {
+                PlayerColor::White => stones_in_hand.0 = amount,
+                PlayerColor::Black => stones_in_hand.1 = amount,
+            }
+        }
+
+        let game_phase = match (tokens[2].1, stones_in_hand) {
+            ("s" | "S", (0, 0)) => return Err(error(tokens[2].0, "The set phase ends when both hands are empty.")),
+            ("s" | "S", _) => GamePhase::Set,
+            ("m" | "M", (0, 0)) => GamePhase::MoveAndJump,
+            ("m" | "M", _) => return Err(error(tokens[2].0, "The move phase starts when both hands are empty.")),
+            ("t" | "T", _) => GamePhase::Terminated,
+            _ => return Err(error(tokens[2].0, "The phase must be either s, m or t.")),
+        };
+
+        let plies_without_mill = match tokens[5].1.parse::<u32>() {
+            Ok(0) => 0,
+            Ok(_) if stones_in_hand != (0, 0) => {
+                return Err(error(tokens[5].0, "Plies without mill are only counted when both hands are empty."))
+            }
+            Ok(plies) => plies,
+            _ => return Err(error(tokens[5].0, "The plies without mill must be a number.")),
+        };
+
+        let round = match tokens[6].1.parse::<u32>() {
+            Ok(round) if 0 < round => round,
+            _ => return Err(error(tokens[6].0, "The round must be a number greater than 0.")),
+        };
+
+        Ok(Position {
+            play_field,
+            side_to_move,
+            stones_in_hand,
+            game_phase,
+            round,
+            plies_without_mill,
+            plies_before_resets: Vec::new(),
+        })
+    }
+}
+
+/// Returns the fields of the board in the order they are written in the notation
+fn notation_order(board: &'static Board) -> Box<dyn Iterator<Item = Field>> {
+    if board.point_count() == FIELD_COUNT {
+        Box::new(FIELD_POS_LUT.iter().flatten().copied())
+    } else {
+        Box::new(board.points.iter().copied())
+    }
+}
+
+/// Splits the input at whitespace, returning the byte index every part starts at alongside it
+fn split_with_indices(input: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut token_start = None;
+
+    for (index, char) in input.char_indices().chain([(input.len(), ' ')]) {
+        match (char.is_whitespace(), token_start) {
+            (true, Some(start)) => {
+                tokens.push((start, &input[start..index]));
+                token_start = None;
+            }
+            (false, None) => token_start = Some(index),
+            _ => (),
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor;
+
+    use super::super::Position;
+    use crate::game::rules::RuleSet;
+    use crate::game::GamePhase;
+
+    #[test]
+    fn parse_and_print_round_trip() {
+        assert_eq!(Position::default().to_string(), "EEEEEEEEEEEEEEEEEEEEEEEE w s 9 9 0 1");
+
+        let mut position = Position::default();
+        for notation in ["d5", "d1", "e4"] {
+            position.apply_move(&notation.parse().unwrap()).unwrap();
+        }
+        let notation = position.to_string();
+        assert_eq!(notation, "WEWEEEEEEEEEEEEEEEEEBEEE b s 7 8 0 4");
+
+        let parsed = notation.parse::<Position>().unwrap();
+        assert!(parsed == position);
+        assert_eq!(parsed.round, 4);
+        assert!(matches!(parsed.side_to_move, PlayerColor::Black));
+        assert_eq!(parsed.play_field.amount_of_stones, (2, 1));
+    }
+
+    #[test]
+    fn small_boards_are_written_point_by_point() {
+        let mut position = Position::new(RuleSet::six_mens_morris());
+        position.apply_move(&"c1".parse().unwrap()).unwrap();
+
+        let notation = position.to_string();
+        assert_eq!(notation, "EWEEEEEEEEEEEEEE b s 5 6 0 2");
+        assert!(Position::from_notation(&notation, RuleSet::six_mens_morris()).unwrap() == position);
+        assert_eq!(Position::from_notation(&notation, RuleSet::default()).err().unwrap().column, 1);
+    }
+
+    #[test]
+    fn parse_errors_point_at_column() {
+        let column = |input: &str| input.parse::<Position>().err().unwrap().column;
+
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEEE w s 9 9 0"), 35);
+        assert_eq!(column("EEEEEEEEEEEXEEEEEEEEEEEE w s 9 9 0 1"), 12);
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEE w s 9 9 0 1"), 1);
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEEE x s 9 9 0 1"), 26);
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEEE w m 9 9 0 1"), 28);
+        assert_eq!(column("WEEEEEEEEEEEEEEEEEEEEEEE w s 9 9 0 1"), 30);
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEEE w s 9 9 0 0"), 36);
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEEE w s 9 9 1 1"), 34);
+        assert_eq!(column("EEEEEEEEEEEEEEEEEEEEEEEE w t 0 0 x 1"), 34);
+
+        let terminated = "EEEEEEEEEEEEEEEEEEEEEEEE w t 0 0 0 1".parse::<Position>().unwrap();
+        assert_eq!(terminated.game_phase, GamePhase::Terminated);
+    }
+}
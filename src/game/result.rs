@@ -0,0 +1,110 @@
+//! Contains the [GameResult], which is the outcome of a game including the reason it ended.
+
+use std::fmt::{self, Display};
+
+use mill_playfield::PlayerColor;
+
+/// The outcome of a terminated game
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameResult {
+    WhiteWins(WinReason),
+    BlackWins(WinReason),
+    Draw(DrawReason),
+}
+
+/// The reason a player won the game
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum WinReason {
+    /// The opponent has less stones on the play field & in his hand than the rules require to continue playing
+    TooFewStones,
+    /// The opponent can't move any of his stones
+    NoLegalMoves,
+    Resignation,
+    /// The opponent ran out of time
+    Timeout,
+}
+
+/// The reason a game ended in a draw
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DrawReason {
+    /// The same position occurred for the third time
+    ThreefoldRepetition,
+    /// The amount of moves set by the [crate::game::rules::DrawConditions] was made without closing a mill
+    MovesWithoutMill,
+    /// Both players agreed on a draw
+    Agreement,
+    /// The player on turn can't move any of his stones, which the rules don't count as a loss
+    Blocked,
+    /// All points of the board got covered in the set phase, so nobody can move
+    FullBoard,
+}
+
+impl GameResult {
+    /// Returns the result of the player with the specified color winning the game
+    pub fn win(winner: PlayerColor, reason: WinReason) -> Self {
+        match winner {
+            PlayerColor::White => GameResult::WhiteWins(reason),
+            PlayerColor::Black => GameResult::BlackWins(reason),
+        }
+    }
+
+    /// Returns the color of the winner, which doesn't exist for draws
+    pub fn winner(&self) -> Option<PlayerColor> {
+        match self {
+            GameResult::WhiteWins(_) => Some(PlayerColor::White),
+            GameResult::BlackWins(_) => Some(PlayerColor::Black),
+            GameResult::Draw(_) => None,
+        }
+    }
+}
+
+/// Prints the result in the notation of the game records, followed by the reason, e.g. `1-0 (The opponent resigned)`
+impl Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::WhiteWins(reason) => f.write_fmt(format_args!("1-0 ({reason})")),
+            GameResult::BlackWins(reason) => f.write_fmt(format_args!("0-1 ({reason})")),
+            GameResult::Draw(reason) => f.write_fmt(format_args!("½-½ ({reason})")),
+        }
+    }
+}
+
+impl Display for WinReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WinReason::TooFewStones => "The opponent has too few stones left",
+            WinReason::NoLegalMoves => "The opponent can't move any of his stones",
+            WinReason::Resignation => "The opponent resigned",
+            WinReason::Timeout => "The opponent ran out of time",
+        })
+    }
+}
+
+impl Display for DrawReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DrawReason::ThreefoldRepetition => "The same position occurred for the third time",
+            DrawReason::MovesWithoutMill => "Too many moves were made without closing a mill",
+            DrawReason::Agreement => "Both players agreed on a draw",
+            DrawReason::Blocked => "The player on turn can't move any of his stones",
+            DrawReason::FullBoard => "All points of the board are covered",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor;
+
+    use super::{DrawReason, GameResult, WinReason};
+
+    #[test]
+    fn winner_of_results() {
+        let result = GameResult::win(PlayerColor::Black, WinReason::Resignation);
+        assert_eq!(result, GameResult::BlackWins(WinReason::Resignation));
+        assert!(matches!(result.winner(), Some(PlayerColor::Black)));
+        assert!(result.to_string().starts_with("0-1"));
+
+        assert!(GameResult::Draw(DrawReason::Agreement).winner().is_none());
+    }
+}
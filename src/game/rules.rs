@@ -0,0 +1,220 @@
+//! Contains the [RuleSet], which bundles the rules differing between the variations of the game played in clubs.
+//! The [Default] rules are the ones this program always implemented.
+
+use crate::game::board::{self, Board};
+
+/// When the stones of the opponent which are part of a closed mill may be taken
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum TakingFromMills {
+    /// Stones in mills are protected, so there might be nothing to take
+    Never,
+    /// Stones in mills can be taken only if all stones of the opponent are in mills
+    IfAllInMills,
+    /// Every stone of the opponent can be taken
+    Always,
+}
+
+/// The conditions under which a game ends in a draw
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct DrawConditions {
+    /// The amount of full moves, in which each player moved a stone once, made in the move phase without closing a
+    /// mill, after which the game is drawn. So the game is drawn after twice as many plies.
+    pub moves_without_mill: Option<u32>,
+    /// If the game is drawn when the same position occurs for the third time
+    pub threefold_repetition: bool,
+    /// If the game is drawn when all points are covered after the set phase, so nobody can move
+    pub full_board: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct RuleSet {
+    pub board: &'static Board,
+    /// The amount of stones every player places in the set phase
+    pub stones_per_player: u32,
+    /// If stones on the play field may be moved instead of placing one while stones are left in the hand
+    pub moving_in_set_phase: bool,
+    /// If players with [RuleSet::flying_stone_count] stones or less left may jump to any free field
+    pub flying: bool,
+    /// The amount of stones on the play field at & below which a player may jump, if [RuleSet::flying] is on
+    pub flying_stone_count: u32,
+    /// Players with this amount of stones or less left on the play field & in their hand lose the game
+    pub losing_stone_count: u32,
+    /// The amount of stones taken when two mills are closed at once, which is either 1 or 2
+    pub takes_on_double_mill: u32,
+    pub taking_from_mills: TakingFromMills,
+    /// If a player who can't move any of his stones loses the game, otherwise the game is drawn
+    pub blocked_loses: bool,
+    pub draw_conditions: DrawConditions,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            board: &board::NINE_MENS_MORRIS,
+            stones_per_player: 9,
+            moving_in_set_phase: false,
+            flying: true,
+            flying_stone_count: 3,
+            losing_stone_count: 2,
+            takes_on_double_mill: 2,
+            taking_from_mills: TakingFromMills::IfAllInMills,
+            blocked_loses: true,
+            draw_conditions: DrawConditions::default(),
+        }
+    }
+}
+
+impl RuleSet {
+    /// The rules commonly used in tournaments: One stone is taken on double mills & long games are drawn
+    pub fn tournament() -> Self {
+        Self {
+            takes_on_double_mill: 1,
+            draw_conditions: DrawConditions {
+                moves_without_mill: Some(50),
+                threefold_repetition: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// The default rules, but stones only slide along the lines until the game ends
+    pub fn without_flying() -> Self {
+        Self { flying: false, ..Default::default() }
+    }
+
+    /// Twelve Men's Morris on the board with diagonals: 12 stones, no flying & a draw if the board fills up while setting
+    pub fn twelve_mens_morris() -> Self {
+        Self {
+            board: &board::TWELVE_MENS_MORRIS,
+            stones_per_player: 12,
+            flying: false,
+            draw_conditions: DrawConditions { full_board: true, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    /// Lasker Morris: 10 stones, which are either placed or a stone on the play field is moved on every turn
+    pub fn lasker() -> Self {
+        Self {
+            stones_per_player: 10,
+            moving_in_set_phase: true,
+            ..Default::default()
+        }
+    }
+
+    /// Three Men's Morris on the smallest board: 3 stones, so closing a mill decides the game, no flying
+    pub fn three_mens_morris() -> Self {
+        Self {
+            board: &board::THREE_MENS_MORRIS,
+            stones_per_player: 3,
+            flying: false,
+            ..Default::default()
+        }
+    }
+
+    /// Six Men's Morris on the board of two squares: 6 stones, no flying
+    pub fn six_mens_morris() -> Self {
+        Self {
+            board: &board::SIX_MENS_MORRIS,
+            stones_per_player: 6,
+            flying: false,
+            ..Default::default()
+        }
+    }
+
+    /// Morabaraba, the South African variant of Twelve Men's Morris: Flying with 3 stones & one take on double mills
+    pub fn morabaraba() -> Self {
+        Self {
+            flying: true,
+            takes_on_double_mill: 1,
+            ..Self::twelve_mens_morris()
+        }
+    }
+
+    /// Returns if a player with the specified amount of stones on the play field may jump
+    pub fn can_fly(&self, stone_count: u32) -> bool {
+        self.flying && stone_count <= self.flying_stone_count
+    }
+
+    /// Returns the amount of stones to take after closing the specified amount of mills at once
+    pub fn takes_for_mills(&self, mills_closed: usize) -> usize {
+        match mills_closed {
+            0 | 1 => mills_closed,
+            _ => self.takes_on_double_mill as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor::{Black, White};
+
+    use super::{RuleSet, TakingFromMills};
+    use crate::game::state::PlayField;
+
+    fn play_field_with(rules: RuleSet, white: &[&str], black: &[&str]) -> PlayField {
+        let mut play_field = PlayField::with_rules(rules);
+        for (fields, color) in [(white, White), (black, Black)] {
+            for field in fields {
+                play_field.try_set(crate::game::moves::parse_field(field).unwrap(), color).unwrap();
+            }
+        }
+        play_field
+    }
+
+    #[test]
+    fn takes_on_double_mill() {
+        let (white, black) = (["d7", "g7", "a1", "a4"], ["b2", "f2", "d3"]);
+
+        let mut standard = play_field_with(RuleSet::default(), &white, &black);
+        assert!(standard.clone().apply_move(&"a7xb2".parse().unwrap(), White).is_err());
+        assert!(standard.apply_move(&"a7xb2xf2".parse().unwrap(), White).is_ok());
+
+        let mut tournament = play_field_with(RuleSet::tournament(), &white, &black);
+        assert!(tournament.clone().apply_move(&"a7xb2xf2".parse().unwrap(), White).is_err());
+        assert!(tournament.apply_move(&"a7xb2".parse().unwrap(), White).is_ok());
+    }
+
+    #[test]
+    fn stones_in_mills_are_protected() {
+        let rules = RuleSet {
+            taking_from_mills: TakingFromMills::Never,
+            ..Default::default()
+        };
+        let mut play_field = play_field_with(rules, &["a1", "a4"], &["b2", "d2", "f2"]);
+
+        assert!(play_field.get_takeable_fields(White).is_empty());
+        assert!(play_field.clone().apply_move(&"a7xd2".parse().unwrap(), White).is_err());
+        assert!(play_field.apply_move(&"a7".parse().unwrap(), White).is_ok());
+    }
+
+    #[test]
+    fn stones_on_the_play_field_are_capped() {
+        let mut play_field = play_field_with(RuleSet::three_mens_morris(), &["a1", "b2", "c1"], &[]);
+        assert!(play_field.try_set(('C', 3), White).is_err());
+        assert!(play_field.try_set(('C', 3), Black).is_ok());
+    }
+
+    #[test]
+    fn flying_can_be_turned_off() {
+        let white = ["a1", "d1", "g4"];
+        let mut without_flying = play_field_with(RuleSet::without_flying(), &white, &["b2"]);
+        assert!(without_flying.try_move(('A', 1), ('D', 5), White).is_err());
+
+        let mut standard = play_field_with(RuleSet::default(), &white, &["b2"]);
+        assert!(standard.try_move(('A', 1), ('D', 5), White).is_ok());
+    }
+
+    #[test]
+    fn diagonal_mills_on_twelve_mens_morris() {
+        let white = ["a1", "b2"];
+        let mut standard = play_field_with(RuleSet::default(), &white, &["d1"]);
+        assert!(standard.apply_move(&"c3xd1".parse().unwrap(), White).is_err());
+
+        let mut twelve_mens_morris = play_field_with(RuleSet::twelve_mens_morris(), &white, &["d1"]);
+        assert!(twelve_mens_morris.apply_move(&"c3xd1".parse().unwrap(), White).is_ok());
+        assert!(twelve_mens_morris.try_move(('C', 3), ('B', 2), White).is_err());
+        assert!(twelve_mens_morris.try_move(('C', 3), ('C', 4), White).is_ok());
+    }
+}
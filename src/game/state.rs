@@ -1,9 +1,16 @@
+pub mod conversion;
+pub mod generation;
+pub mod history;
 pub mod printing;
 pub mod representation;
 
+use std::hash::{Hash, Hasher};
+
 use mill_playfield::PlayerColor;
 
 use self::representation::types::*;
+use crate::game::moves::Move;
+use crate::game::rules::{RuleSet, TakingFromMills};
 use crate::game::Field;
 
 #[derive(Debug)]
@@ -31,15 +38,54 @@ pub enum PlayFieldError {
         field: Field,
         message: &'static str,
     },
+    InvalidTakeCountError {
+        mills_closed: usize,
+        takes: usize,
+        message: &'static str,
+    },
+    ConversionError {
+        field: Field,
+        message: &'static str,
+    },
+    InvalidPhaseError {
+        message: &'static str,
+    },
+    UnsupportedBoardError {
+        board: &'static str,
+        message: &'static str,
+    },
+    UnsupportedStoneCountError {
+        stones_per_player: u32,
+        max_stones: usize,
+    },
 }
 
 use self::representation::constants::*;
 
-
+#[derive(Clone)]
 pub struct PlayField {
     state: [FieldState; FIELD_COUNT],
     // first one: white, second one: black
     pub amount_of_stones: (u32, u32),
+    // The moves applied by [apply_move] & the moves taken back by [undo], which might be redone
+    history: Vec<(Move, PlayerColor)>,
+    undone_moves: Vec<(Move, PlayerColor)>,
+    rules: RuleSet,
+}
+
+/// Two [PlayField]s are equal if the stones on them are, no matter how they got there or which rules apply
+impl PartialEq for PlayField {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl Eq for PlayField {}
+
+impl Hash for PlayField {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.state.hash(state);
+    }
 }
 
 impl Default for PlayField {
@@ -47,23 +93,51 @@ impl Default for PlayField {
         Self {
             state: [FieldState::Free; FIELD_COUNT],
             amount_of_stones: (0, 0),
+            history: Vec::new(),
+            undone_moves: Vec::new(),
+            rules: RuleSet::default(),
         }
     }
 }
 
 impl PlayField {
+    /// Returns an empty [PlayField] on which the specified rules apply
+    pub fn with_rules(rules: RuleSet) -> Self {
+        Self { rules, ..Default::default() }
+    }
+
+    pub fn get_rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Returns the amount of stones the player of the specified color has on the play field
+    pub fn get_amount_of_stones(&self, color: PlayerColor) -> u32 {
+        match color {
+            PlayerColor::White => self.amount_of_stones.0,
+            PlayerColor::Black => self.amount_of_stones.1,
+        }
+    }
+
     /// Sets a sone to the specified position by calling the [get_status_of] method.
     /// Then modifies the interior [state] array
     ///
     /// Handled extreme cases:
     /// - The selected field is not empty
+    /// - The player has as many stones on the play field as the [RuleSet] gives him. This only caps the stones on the
+    ///   board, as stones taken aren't known to the [PlayField]. The stones left in the hand are counted by the
+    ///   [crate::game::position::Position].
     pub fn try_set(&mut self, pos: Field, color: PlayerColor) -> Result<(), PlayFieldError> {
         use FieldState::*;
 
-        assert!(self.amount_of_stones.0 < 10 && self.amount_of_stones.1 < 10);
-
         let current_state = self.get_status_of(pos)?;
-        if matches!(current_state, Free) {
+        if self.rules.stones_per_player <= self.get_amount_of_stones(color) {
+            Err(PlayFieldError::FieldSetError {
+                player: color,
+                field: pos,
+                field_state: current_state,
+                message: "The player already has as many stones on the play field as the rules give him.",
+            })
+        } else if matches!(current_state, Free) {
             let index_to_change = self.map_to_state_index(pos)?;
 
             self.state[index_to_change] = color.into();
@@ -83,97 +157,135 @@ impl PlayField {
     }
 
     /// First method called when a player tries to move a stone from one field to another.
-    /// It permits the move by calling self.move if the amount of stones == 3 or if the move occurs vertically & exactly one field difference.
+    /// It permits the move by calling self.move if the player is allowed to fly by the [RuleSet] or if the fields are
+    /// neighbors on the board.
     ///
     /// Handles the move in context of the game state:
-    /// - If the player has more than 3 stones, it should not be possible to jump
     /// - The stone can't be moved to it's own field
-    /// - The stone can't be moved more than one field in a direction
-    /// - All other states are not allowed
+    /// - If the player has more than 3 stones or flying is off, it should not be possible to jump
+    /// - The stone can only be moved along a line to the next field
     pub fn try_move(&mut self, start_pos: Field, target_pos: Field, color: PlayerColor) -> Result<(), PlayFieldError> {
-        let players_stone_count = match color {
-            PlayerColor::White => self.amount_of_stones.0,
-            PlayerColor::Black => self.amount_of_stones.1,
-        };
+        let players_stone_count = self.get_amount_of_stones(color);
 
-        if let Err(err) = self.map_to_state_index(start_pos) {
-            return Err(err);
-        } else if let Err(err) = self.map_to_state_index(target_pos) {
-            return Err(err);
-        }
+        let start_index = self.map_to_state_index(start_pos)?;
+        let target_index = self.map_to_state_index(target_pos)?;
 
-        // Jumps, with more than 3 stones
-        if 4 <= players_stone_count && start_pos.0 != target_pos.0 && start_pos.1 != target_pos.1 {
+        // Move to same field
+        if start_index == target_index {
             Err(PlayFieldError::InvalidMovementError {
                 start_field: start_pos,
                 target_field: target_pos,
                 player_color: color,
-                message: "The movement of a stone must occur horizontally or vertically.",
+                message: "The stone can't stay on the same field after moving.",
             })
-        // Move to same field
-        } else if start_pos.0 == target_pos.0 && start_pos.1 == target_pos.1 {
+        // Jumps or moves along a line to the next field
+        } else if self.rules.can_fly(players_stone_count) || self.rules.board.are_neighbors(start_index, target_index) {
+            self.r#move(start_pos, target_pos, color)
+        } else {
             Err(PlayFieldError::InvalidMovementError {
                 start_field: start_pos,
                 target_field: target_pos,
                 player_color: color,
-                message: "The stone can't stay on the same field after moving.",
+                message: "The stone can only be moved along a line to the next field.",
             })
-        // Jumps
-        } else if players_stone_count == 3 {
-            self.r#move(start_pos, target_pos, color)
-        // Moves in one direction
-        } else if (start_pos.0 == target_pos.0) ^ (start_pos.1 == target_pos.1) {
-            // if the letter stays the same, the letter is constant & specifies the layer the move should take place
-            let (layer_to_move_on_as_u8, constant_coord) =
-                if start_pos.0 == target_pos.0 { ((start_pos.0 as u8) - b'A', false) } else { (start_pos.1 - 1, true) };
-            //println!("layer: {}, constant coord: {}", layer_to_move_on_as_u8, constant_coord);
-
-            let legal_move_range = match layer_to_move_on_as_u8 {
-                0 | 6 => 3,
-                1 | 5 => 2,
-                2 | 4 => 1,
-                3 => 1, // <- took an hour or so
-                _ => panic!(),
-            };
-            // println!("Legal move range: {}", legal_move_range);
-
-            match constant_coord {
-                false => {
-                    //println!("Calculating delta: {}", (start_pos.1 as i16 - target_pos.1 as i16).abs());
-                    if ((start_pos.1 as i16) - (target_pos.1 as i16)).unsigned_abs() != legal_move_range {
-                        return Err(PlayFieldError::InvalidMovementError {
-                            start_field: start_pos,
-                            target_field: target_pos,
-                            player_color: color,
-                            message: "Stone can't be moved multiple fields vertical (letter is constant) ahead.",
-                        });
-                    }
-                }
-                true => {
-                    //println!("Calculating delta: {}", (start_pos.0 as i16 - target_pos.0 as i16).abs());
-                    if (start_pos.0 as i16 - target_pos.0 as i16).unsigned_abs() != legal_move_range {
-                        return Err(PlayFieldError::InvalidMovementError {
-                            start_field: start_pos,
-                            target_field: target_pos,
-                            player_color: color,
-                            message: "Stone can't be moved multiple fields horizontal (number is constant) ahead.",
-                        });
-                    }
+        }
+    }
+
+    /// Applies the complete [Move] of the player with the specified color atomically: If placing or moving the stone,
+    /// or one of the takes fails, the [PlayField] stays untouched.
+    /// The move is appended to the history, which makes it possible to [undo] it.
+    ///
+    /// Handled extreme cases additionally to the ones of the try_... functions:
+    /// - The move is a jump, but the player isn't allowed to fly
+    /// - The move takes more stones than the closed mills permit
+    /// - The move takes less stones than the closed mills permit, although the opponent has stones left to take
+    pub fn apply_move(&mut self, r#move: &Move, color: PlayerColor) -> Result<(), PlayFieldError> {
+        // The history isn't needed for the simulation & would be copied on every move
+        let mut simulation = self.clone_without_history();
+        simulation.try_set_or_move(r#move, color)?;
+
+        let mills_closed = simulation.get_mill_crossing(r#move.target_field()).len() / 3;
+        let takes = r#move.takes().len();
+
+        if self.rules.takes_for_mills(mills_closed) < takes {
+            return Err(PlayFieldError::InvalidTakeCountError {
+                mills_closed,
+                takes,
+                message: "Only one stone can be taken for every closed mill, or one for both on a double mill.",
+            });
+        }
+
+        for field_to_take in r#move.takes() {
+            simulation.try_take(*field_to_take, color)?;
+        }
+
+        if takes < self.rules.takes_for_mills(mills_closed) && !simulation.get_takeable_fields(color).is_empty() {
+            return Err(PlayFieldError::InvalidTakeCountError {
+                mills_closed,
+                takes,
+                message: "A stone of the opponent must be taken for every closed mill.",
+            });
+        }
+
+        self.state = simulation.state;
+        self.amount_of_stones = simulation.amount_of_stones;
+        self.history.push((r#move.clone(), color));
+        self.undone_moves.clear();
+        Ok(())
+    }
+
+    /// Places or moves the stone of the [Move] by calling [try_set] or [try_move], ignoring the moves takes.
+    /// This is used if the stones to take are determined after the stone was placed or moved.
+    pub fn try_set_or_move(&mut self, r#move: &Move, color: PlayerColor) -> Result<(), PlayFieldError> {
+        match *r#move {
+            Move::Place { field, .. } => self.try_set(field, color),
+            Move::Slide { start_field, target_field, .. } => self.try_move(start_field, target_field, color),
+            Move::Jump { start_field, target_field, .. } => {
+                if !self.rules.can_fly(self.get_amount_of_stones(color)) {
+                    return Err(PlayFieldError::InvalidMovementError {
+                        start_field,
+                        target_field,
+                        player_color: color,
+                        message: "Jumps are only permitted with 3 stones left & if the rules allow flying.",
+                    });
                 }
+                self.try_move(start_field, target_field, color)
             }
-
-            // Exactly one move
-            self.r#move(start_pos, target_pos, color)
-        // Any other?
-        } else {
-            Err(PlayFieldError::InvalidProgramStateError {
-                message: "State should never be reached in try_move method. Might be caused an stone count < 3. Programmer's dumb.",
-            })
         }
     }
 
+    /// Checks if the player of the specified color is able to move at least one of his stones.
+    /// With 3 stones left, the player might be allowed to jump, which is possible as long as a field is free.
+    ///
+    /// A player who isn't able to move any stone has lost the game, or drawn it depending on the [RuleSet].
+    pub fn can_move(&self, color: PlayerColor) -> bool {
+        let can_fly = self.rules.can_fly(self.get_amount_of_stones(color));
+
+        let point_count = self.rules.board.point_count();
+
+        (0..point_count).filter(|&index| self.state[index] == color.into()).any(|index| {
+            if can_fly {
+                self.state[..point_count].contains(&FieldState::Free)
+            } else {
+                self.rules.board.neighbors[index].iter().any(|&neighbor| self.state[neighbor] == FieldState::Free)
+            }
+        })
+    }
+
+    /// Returns true if every stone of the specified color on the [PlayField] is element of at least one closed mill.
+    /// In this case, the stones of this color are allowed to be taken out of their mills.
+    pub fn all_stones_in_mills(&self, color: PlayerColor) -> bool {
+        self.rules
+            .board
+            .points
+            .iter()
+            .filter(|&&field| self.get_status_of(field).unwrap() == color.into())
+            .all(|&field| !self.get_mill_crossing(field).is_empty())
+    }
+
     /// As the other try_... function, this one also checks cases in the context of the player's color &
-    /// if it's permitted to proceed taking the specified stone
+    /// if it's permitted to proceed taking the specified stone.
+    /// Stones in closed mills are only permitted to be taken as the [TakingFromMills] rule says.
     pub fn try_take(&mut self, field_to_take: Field, player_color: PlayerColor) -> Result<(), PlayFieldError> {
         let field_state = match self.get_status_of(field_to_take) {
             Ok(state) => state,
@@ -186,11 +298,14 @@ impl PlayField {
         };
 
         if field_state != player_color.into() && field_state != FieldState::Free {
-            // If the field to take is in a mill
-            if !self.get_mill_crossing(field_to_take).is_empty() {
+            // If the field to take is in a mill, which is only permitted if all stones of the opponent are in mills
+            if !self.get_mill_crossing(field_to_take).is_empty() && !self.may_take_from_mills(player_color) {
                 return Err(PlayFieldError::FailedToTake {
                     field: field_to_take,
-                    message: "The specified stone to take is in at lease one mill.",
+                    message: match self.rules.taking_from_mills {
+                        TakingFromMills::Never => "The specified stone to take is in at lease one mill.",
+                        _ => "The specified stone to take is in at lease one mill, but not all opponent stones are.",
+                    },
                 });
             }
             self.take(field_to_take);
@@ -205,6 +320,15 @@ impl PlayField {
 }
 
 impl PlayField {
+    /// Returns if the player with the specified color may take the opponents stones which are part of mills
+    fn may_take_from_mills(&self, player_color: PlayerColor) -> bool {
+        match self.rules.taking_from_mills {
+            TakingFromMills::Never => false,
+            TakingFromMills::IfAllInMills => self.all_stones_in_mills(!player_color),
+            TakingFromMills::Always => true,
+        }
+    }
+
     // Handles the move in context of the state of the game field, covering the following extreme cases:
     // - The start field doesn't contain a stone of the players color
     // - The target field isn't empty
@@ -0,0 +1,175 @@
+//! Contains the conversion between the [PlayField] & the bit packed [EfficientPlayField], including the mapping of the
+//! player visible [Field] notation to the [FieldPos] of the rings.
+//!
+//! The [EfficientPlayField] always consists of 3 rings of 8 fields without diagonals, so only [PlayField]s of the
+//! Nine Men's Morris board can be converted.
+//!
+//! The rings of the [EfficientPlayField] are indexed from the inner one (0) to the outer one (2). The fields of
+//! each ring are indexed clockwise, starting at its top middle field. The even indices therefore are the middle
+//! fields, which are connected across the rings.
+
+use mill_playfield::{EfficientPlayField, FieldPos, FieldState as EfficientFieldState, PlayerColor};
+
+use super::representation::constants::FIELD_LUT;
+use super::{FieldState, PlayField, PlayFieldError};
+use crate::game::board::{MILLS, NEIGHBORS};
+use crate::game::Field;
+
+/// The [Field] of every [FieldPos], indexed by `[ring_index][index]`
+#[rustfmt::skip]
+pub const FIELD_POS_LUT: [[Field; 8]; 3] = [
+    [('D',5), ('E',5), ('E',4), ('E',3), ('D',3), ('C',3), ('C',4), ('C',5)],
+    [('D',6), ('F',6), ('F',4), ('F',2), ('D',2), ('B',2), ('B',4), ('B',6)],
+    [('D',7), ('G',7), ('G',4), ('G',1), ('D',1), ('A',1), ('A',4), ('A',7)],
+];
+
+/// Maps a [Field] to the [FieldPos] of the [EfficientPlayField], if the field is a valid game field
+pub fn field_to_field_pos(field: Field) -> Option<FieldPos> {
+    FIELD_POS_LUT.iter().enumerate().find_map(|(ring_index, ring)| {
+        ring.iter().position(|&ring_field| ring_field == field).map(|index| FieldPos { ring_index, index })
+    })
+}
+
+/// Maps a [FieldPos] of the [EfficientPlayField] to the [Field] notation
+pub fn field_pos_to_field(field_pos: FieldPos) -> Field {
+    FIELD_POS_LUT[field_pos.ring_index][field_pos.index]
+}
+
+/// Handled extreme cases:
+/// - The [PlayField] is one of a board with less than 24 points
+/// - The board connects its points diagonally, which the [EfficientPlayField] can't represent
+impl TryFrom<&PlayField> for EfficientPlayField {
+    type Error = PlayFieldError;
+
+    fn try_from(play_field: &PlayField) -> Result<Self, Self::Error> {
+        let board = play_field.get_rules().board;
+        if board.points != FIELD_LUT {
+            return Err(PlayFieldError::UnsupportedBoardError {
+                board: board.name,
+                message: "Only play fields of boards with 24 points can be converted to an EfficientPlayField.",
+            });
+        }
+        if board.neighbors != NEIGHBORS || board.mills != MILLS {
+            return Err(PlayFieldError::UnsupportedBoardError {
+                board: board.name,
+                message: "The diagonals of the board can't be represented by an EfficientPlayField.",
+            });
+        }
+
+        let mut efficient_play_field = EfficientPlayField::default();
+
+        for (index, field) in FIELD_LUT.iter().enumerate() {
+            let field_state = match play_field.state[index] {
+                FieldState::Free => continue,
+                FieldState::White => EfficientFieldState::White,
+                FieldState::Black => EfficientFieldState::Black,
+            };
+            efficient_play_field.set_field_state(field_to_field_pos(*field).unwrap(), field_state);
+        }
+
+        Ok(efficient_play_field)
+    }
+}
+
+impl TryFrom<PlayField> for EfficientPlayField {
+    type Error = PlayFieldError;
+
+    fn try_from(play_field: PlayField) -> Result<Self, Self::Error> {
+        EfficientPlayField::try_from(&play_field)
+    }
+}
+
+/// The history of the [PlayField] is empty after the conversion, the amount of stones is recalculated.
+///
+/// Handled extreme cases:
+/// - A field of the [EfficientPlayField] is in the invalid state `0b11`
+impl TryFrom<&EfficientPlayField> for PlayField {
+    type Error = PlayFieldError;
+
+    fn try_from(efficient_play_field: &EfficientPlayField) -> Result<Self, Self::Error> {
+        let white: u16 = PlayerColor::White.into();
+        let black: u16 = PlayerColor::Black.into();
+
+        let mut play_field = PlayField::default();
+
+        for ring_index in 0..3 {
+            for index in 0..8 {
+                let field_pos = FieldPos { ring_index, index };
+                let field = field_pos_to_field(field_pos);
+                // The state of the field shifted to the LSB
+                let field_state = efficient_play_field.get_field_state_at(field_pos) >> (index * 2);
+
+                let color = match field_state {
+                    0 => continue,
+                    state if state == white => PlayerColor::White,
+                    state if state == black => PlayerColor::Black,
+                    _ => {
+                        return Err(PlayFieldError::ConversionError {
+                            field,
+                            message: "The field of the EfficientPlayField has an invalid state.",
+                        })
+                    }
+                };
+
+                let state_index = play_field.map_to_state_index(field)?;
+                play_field.state[state_index] = color.into();
+                match color {
+                    PlayerColor::White => play_field.amount_of_stones.0 += 1,
+                    PlayerColor::Black => play_field.amount_of_stones.1 += 1,
+                }
+            }
+        }
+
+        Ok(play_field)
+    }
+}
+
+impl TryFrom<EfficientPlayField> for PlayField {
+    type Error = PlayFieldError;
+
+    fn try_from(efficient_play_field: EfficientPlayField) -> Result<Self, Self::Error> {
+        PlayField::try_from(&efficient_play_field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor::{Black, White};
+    use mill_playfield::{EfficientPlayField, FieldPos};
+
+    use super::{field_pos_to_field, field_to_field_pos};
+    use crate::game::rules::RuleSet;
+    use crate::game::state::representation::constants::FIELD_LUT;
+    use crate::game::state::PlayField;
+
+    #[test]
+    fn field_mapping_round_trip() {
+        for field in FIELD_LUT {
+            assert_eq!(field_pos_to_field(field_to_field_pos(field).unwrap()), field);
+        }
+        assert!(field_to_field_pos(('B', 1)).is_none());
+
+        // The middle fields are connected across the rings
+        assert_eq!(field_pos_to_field(FieldPos { ring_index: 1, index: 2 }), ('F', 4));
+    }
+
+    #[test]
+    fn play_field_round_trip() {
+        let mut play_field = PlayField::default();
+        for (notation, color) in [("a1", White), ("d2", Black), ("e4", White), ("g7", Black), ("c5", White)] {
+            play_field.apply_move(&notation.parse().unwrap(), color).unwrap();
+        }
+
+        let efficient_play_field = EfficientPlayField::try_from(&play_field).unwrap();
+        let converted_back = PlayField::try_from(&efficient_play_field).unwrap();
+
+        assert!(converted_back == play_field);
+        assert_eq!(converted_back.amount_of_stones, (3, 2));
+        assert_eq!(EfficientPlayField::try_from(&converted_back).unwrap(), efficient_play_field);
+
+        let six_mens_morris = PlayField::with_rules(RuleSet::six_mens_morris());
+        assert!(EfficientPlayField::try_from(&six_mens_morris).is_err());
+        let twelve_mens_morris = PlayField::with_rules(RuleSet::twelve_mens_morris());
+        assert!(EfficientPlayField::try_from(&twelve_mens_morris).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Contains the legal move generator of the [PlayField], enumerating every legal turn of a player as [Move].
+
+use mill_playfield::PlayerColor;
+use smallvec::SmallVec;
+
+use super::representation::constants::FIELD_COUNT;
+use super::{FieldState, PlayField};
+use crate::game::moves::{Move, Takes};
+use crate::game::Field;
+
+impl PlayField {
+    /// Generates every legal [Move] of the player with the specified color.
+    /// If the player has stones left in his hand, placements are generated, as well as slides if the rules permit
+    /// moving in the set phase. Otherwise the player slides his stones, or jumps with them if he only has 3 stones
+    /// left & the rules allow flying.
+    ///
+    /// Moves closing mills are generated once for every legal combination of stones to take.
+    pub fn generate_moves(&self, color: PlayerColor, stones_in_hand: u32) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let board = self.rules.board;
+        let free_indices = || (0..board.point_count()).filter(|&index| self.state[index] == FieldState::Free);
+
+        if 0 < stones_in_hand {
+            for target_index in free_indices() {
+                let field = board.points[target_index];
+
+                let mut simulation = self.clone_without_history();
+                simulation.state[target_index] = color.into();
+                match color {
+                    PlayerColor::White => simulation.amount_of_stones.0 += 1,
+                    PlayerColor::Black => simulation.amount_of_stones.1 += 1,
+                }
+
+                simulation.push_with_takes(Move::Place { field, takes: Takes::new() }, color, &mut moves);
+            }
+
+            if !self.rules.moving_in_set_phase {
+                return moves;
+            }
+        }
+
+        let can_jump = stones_in_hand == 0 && self.rules.can_fly(self.get_amount_of_stones(color));
+
+        for start_index in (0..board.point_count()).filter(|&index| self.state[index] == color.into()) {
+            let start_field = board.points[start_index];
+
+            let target_indices: SmallVec<[usize; FIELD_COUNT]> = if can_jump {
+                free_indices().collect()
+            } else {
+                board.neighbors[start_index]
+                    .iter()
+                    .copied()
+                    .filter(|&index| self.state[index] == FieldState::Free)
+                    .collect()
+            };
+
+            for target_index in target_indices {
+                let target_field = board.points[target_index];
+
+                let mut simulation = self.clone_without_history();
+                simulation.state.swap(start_index, target_index);
+
+                let r#move = if can_jump {
+                    Move::Jump { start_field, target_field, takes: Takes::new() }
+                } else {
+                    Move::Slide { start_field, target_field, takes: Takes::new() }
+                };
+                simulation.push_with_takes(r#move, color, &mut moves);
+            }
+        }
+
+        moves
+    }
+
+    /// Returns the fields of all opponent stones the player with the specified color is permitted to take.
+    /// These are the stones which aren't part of a mill, and the ones in mills if the rules permit taking them.
+    pub fn get_takeable_fields(&self, color: PlayerColor) -> SmallVec<[Field; 12]> {
+        let opponent_fields = self
+            .rules
+            .board
+            .points
+            .iter()
+            .copied()
+            .filter(|&field| self.get_status_of(field).unwrap() == (!color).into());
+
+        if self.may_take_from_mills(color) {
+            opponent_fields.collect()
+        } else {
+            opponent_fields.filter(|&field| self.get_mill_crossing(field).is_empty()).collect()
+        }
+    }
+
+    /// Pushes the specified move onto the moves, for every combination of stones which can be taken after it.
+    /// [self] must be the play field after the stone of the move has been placed or moved.
+    fn push_with_takes(self, r#move: Move, color: PlayerColor, moves: &mut Vec<Move>) {
+        let amount_of_takes = self.rules.takes_for_mills(self.get_mill_crossing(r#move.target_field()).len() / 3);
+        let mut take_combinations = vec![(self, Takes::new())];
+
+        for _ in 0..amount_of_takes {
+            let mut next_take_combinations: Vec<(PlayField, Takes)> = Vec::new();
+
+            for (play_field, takes) in take_combinations.iter() {
+                for field_to_take in play_field.get_takeable_fields(color) {
+                    let mut takes = takes.clone();
+                    takes.push(field_to_take);
+
+                    // Taking the same stones in another order leads to the same play field
+                    let is_duplicate = next_take_combinations.iter().any(|(_, other_takes)| {
+                        other_takes.len() == takes.len() && takes.iter().all(|field| other_takes.contains(field))
+                    });
+                    if is_duplicate {
+                        continue;
+                    }
+
+                    let mut simulation = play_field.clone();
+                    simulation.take(field_to_take);
+                    next_take_combinations.push((simulation, takes));
+                }
+            }
+
+            // The opponent has no stones left which could be taken
+            if next_take_combinations.is_empty() {
+                break;
+            }
+            take_combinations = next_take_combinations;
+        }
+
+        moves.extend(take_combinations.into_iter().map(|(_, takes)| r#move.clone().with_takes(takes)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor;
+
+    use crate::game::moves::Move;
+    use crate::game::state::representation::types::FieldState;
+    use crate::game::state::PlayField;
+
+    #[test]
+    fn placements_on_empty_play_field() {
+        let play_field = PlayField::default();
+
+        assert_eq!(play_field.generate_moves(PlayerColor::White, 9).len(), 24);
+    }
+
+    #[test]
+    fn placement_closing_mill_generates_takes() {
+        let mut play_field = PlayField::default();
+        play_field.try_set(('A', 1), PlayerColor::White).unwrap();
+        play_field.try_set(('D', 1), PlayerColor::White).unwrap();
+        play_field.try_set(('B', 2), PlayerColor::Black).unwrap();
+        play_field.try_set(('D', 2), PlayerColor::Black).unwrap();
+
+        let moves = play_field.generate_moves(PlayerColor::White, 7);
+        let mill_closing_moves =
+            moves.iter().filter(|r#move| r#move.target_field() == ('G', 1)).cloned().collect::<Vec<_>>();
+
+        // 20 free fields, whereby closing the mill on G1 can be done taking B2 or D2
+        assert_eq!(moves.len(), 21);
+        assert_eq!(mill_closing_moves.len(), 2);
+        assert!(mill_closing_moves
+            .iter()
+            .all(|r#move| matches!(r#move, Move::Place { .. }) && r#move.takes().len() == 1));
+    }
+
+    #[test]
+    fn generated_moves_are_applicable() {
+        let mut play_field = PlayField::default();
+        play_field.try_set(('A', 1), PlayerColor::White).unwrap();
+        play_field.try_set(('D', 1), PlayerColor::White).unwrap();
+        play_field.try_set(('B', 2), PlayerColor::Black).unwrap();
+
+        for r#move in play_field.generate_moves(PlayerColor::White, 7) {
+            assert!(play_field.clone().apply_move(&r#move, PlayerColor::White).is_ok(), "{move} failed");
+        }
+
+        // Closing the mill without taking a stone isn't permitted, the play field stays untouched on errors
+        assert!(play_field.apply_move(&"g1".parse().unwrap(), PlayerColor::White).is_err());
+        assert_eq!(play_field.get_status_of(('G', 1)).unwrap(), FieldState::Free);
+    }
+
+    #[test]
+    fn blocked_player_has_no_moves() {
+        let mut play_field = PlayField::default();
+        for field in [('A', 1), ('D', 1), ('G', 1), ('G', 4)] {
+            play_field.try_set(field, PlayerColor::White).unwrap();
+        }
+        for field in [('A', 4), ('D', 2), ('G', 7), ('F', 4)] {
+            play_field.try_set(field, PlayerColor::Black).unwrap();
+        }
+
+        assert!(play_field.generate_moves(PlayerColor::White, 0).is_empty());
+        assert!(!play_field.can_move(PlayerColor::White));
+    }
+}
@@ -0,0 +1,98 @@
+//! Contains the history of the moves applied to the [PlayField], making it possible to undo & redo them.
+
+use std::mem;
+
+use mill_playfield::PlayerColor;
+
+use super::{FieldState, PlayField};
+use crate::game::moves::Move;
+
+impl PlayField {
+    /// Returns the moves applied by [apply_move] & the color of the player who made them, oldest first
+    pub fn get_history(&self) -> &[(Move, PlayerColor)] {
+        &self.history
+    }
+
+    /// Takes back the last applied move by removing the placed stone or moving it back & restoring the taken stones.
+    /// Returns the move taken back and the color of the player who made it, if there was a move to take back.
+    pub fn undo(&mut self) -> Option<(Move, PlayerColor)> {
+        let (r#move, color) = self.history.pop()?;
+
+        for field_taken in r#move.takes() {
+            let index = self.map_to_state_index(*field_taken).unwrap();
+            self.state[index] = (!color).into();
+            *self.get_amount_of_stones_mut(!color) += 1;
+        }
+
+        match r#move {
+            Move::Place { field, .. } => {
+                let index = self.map_to_state_index(field).unwrap();
+                self.state[index] = FieldState::Free;
+                *self.get_amount_of_stones_mut(color) -= 1;
+            }
+            Move::Slide { start_field, target_field, .. } | Move::Jump { start_field, target_field, .. } => {
+                self.swap(target_field, start_field).unwrap();
+            }
+        }
+
+        self.undone_moves.push((r#move.clone(), color));
+        Some((r#move, color))
+    }
+
+    /// Applies the move which was taken back last by [undo] again.
+    /// Returns the move and the color of the player who made it, if there was a move to redo.
+    pub fn redo(&mut self) -> Option<(Move, PlayerColor)> {
+        let (r#move, color) = self.undone_moves.pop()?;
+
+        // Applying a move clears the moves which can be redone, which must be kept here
+        let undone_moves = mem::take(&mut self.undone_moves);
+        self.apply_move(&r#move, color).expect("Moves which were legal before being taken back must stay legal.");
+        self.undone_moves = undone_moves;
+
+        Some((r#move, color))
+    }
+
+    /// Returns a copy of the [PlayField] without its history, which is cheaper for simulating moves
+    pub(crate) fn clone_without_history(&self) -> PlayField {
+        PlayField {
+            state: self.state,
+            amount_of_stones: self.amount_of_stones,
+            rules: self.rules,
+            ..Default::default()
+        }
+    }
+
+    fn get_amount_of_stones_mut(&mut self, color: PlayerColor) -> &mut u32 {
+        match color {
+            PlayerColor::White => &mut self.amount_of_stones.0,
+            PlayerColor::Black => &mut self.amount_of_stones.1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mill_playfield::PlayerColor;
+
+    use crate::game::state::PlayField;
+
+    #[test]
+    fn undo_restores_taken_stones() {
+        let mut play_field = PlayField::default();
+        for (notation, color) in [("a1", PlayerColor::White), ("b2", PlayerColor::Black), ("d1", PlayerColor::White)] {
+            play_field.apply_move(&notation.parse().unwrap(), color).unwrap();
+        }
+        play_field.apply_move(&"g1xb2".parse().unwrap(), PlayerColor::White).unwrap();
+        assert_eq!(play_field.amount_of_stones, (3, 0));
+
+        let (undone_move, _) = play_field.undo().unwrap();
+        assert_eq!(undone_move.to_string(), "g1xb2");
+        assert_eq!(play_field.amount_of_stones, (2, 1));
+        assert!(play_field.get_mill_crossing(('D', 1)).is_empty());
+
+        play_field.redo().unwrap();
+        assert_eq!(play_field.amount_of_stones, (3, 0));
+        assert!(play_field.redo().is_none());
+        assert_eq!(play_field.get_history().len(), 4);
+    }
+}
@@ -1,10 +1,7 @@
 //! Contains everything related to the "low abstraction" of the [PlayField] printing/ painting.
 use core::fmt;
 use std::fmt::Display;
-use std::iter::{Enumerate, Rev};
-use std::slice::Iter;
 
-use either::Either;
 use smallvec::SmallVec;
 
 use super::{FieldState, PlayField, PlayFieldError};
@@ -43,23 +40,29 @@ impl Display for PlayFieldError {
             PlayFieldError::FailedToTake { field, message } => {
                 f.write_fmt(format_args!("Error taking field {}{} - {message}", field.0, field.1))
             }
+            PlayFieldError::InvalidTakeCountError { mills_closed, takes, message } => {
+                f.write_fmt(format_args!("Error taking {takes} stones after closing {mills_closed} mills - {message}"))
+            }
+            PlayFieldError::ConversionError { field, message } => {
+                f.write_fmt(format_args!("Error converting field {}{} - {message}", field.0, field.1))
+            }
+            PlayFieldError::InvalidPhaseError { message } => {
+                f.write_fmt(format_args!("Error caused by the game phase - {message}"))
+            }
+            PlayFieldError::UnsupportedBoardError { board, message } => {
+                f.write_fmt(format_args!("Error caused by the board of {board} - {message}"))
+            }
+            PlayFieldError::UnsupportedStoneCountError { stones_per_player, max_stones } => f.write_fmt(format_args!(
+                "Error caused by {stones_per_player} stones per player - At most {max_stones} stones are supported."
+            )),
         }
     }
 }
 
-type EitherIter<'a> = Either<Rev<Iter<'a, FieldState>>, Enumerate<Rev<Iter<'a, FieldState>>>>;
-
 impl PlayField {
-    /// Printing from top left to bottom right, representation in memory is left bottom to to right
+    /// Prints the play field by filling the points of the boards ASCII template from the top row to the bottom one.
+    /// The stones on the specified fields are highlighted.
     pub fn print_highlighted(&self, fields_to_highlight: Option<&[Field]>) {
-        let mut iter = if fields_to_highlight.is_none() {
-            EitherIter::Left(self.state.iter().rev())
-        } else {
-            Either::Right(self.state.iter().rev().enumerate())
-        };
-
-        let mut row_counter = 7;
-
         // Chose 3 because this is the maximum to highlight positions: two crossing mills is really rare...
         let mut indices_to_highlight = SmallVec::<[usize; 3]>::new();
         // Transform the array of fields into a list of representation array indices
@@ -70,78 +73,39 @@ impl PlayField {
             }
         }
 
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  {}------------{}------------{}", row_counter, c, b, a);
-        row_counter -= 1;
-        println!("\t |  |            |            |");
+        let board = self.rules.board;
+        let mut row_counter = board.points.iter().map(|point| point.1).max().unwrap();
 
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  |   {}--------{}--------{}   |", row_counter, c, b, a);
-        row_counter -= 1;
-        println!("\t |  |   |        |        |   |");
-
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  |   |   {}----{}----{}   |   |", row_counter, c, b, a);
-        row_counter -= 1;
-        println!("\t |  |   |   |         |   |   |");
-
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let d = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let e = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let f = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  {}---{}---{}         {}---{}---{}", row_counter, f, e, d, c, b, a);
-        row_counter -= 1;
-        println!("\t |  |   |   |         |   |   |");
-
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  |   |   {}----{}----{}   |   |", row_counter, c, b, a);
-        row_counter -= 1;
-        println!("\t |  |   |        |        |   |");
+        for (line_index, template_line) in board.ascii_template.iter().enumerate() {
+            // Lines with odd indices only contain the connections between the rows
+            if line_index % 2 == 1 {
+                println!("\t |  {}", template_line);
+                continue;
+            }
 
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  |   {}--------{}--------{}   |", row_counter, c, b, a);
-        row_counter -= 1;
-        println!("\t |  |            |            |");
+            let mut points_in_row = board.points_in_row(row_counter);
+            let line = template_line
+                .chars()
+                .map(|c| match c {
+                    '@' => self.paint_point(points_in_row.next().unwrap(), &indices_to_highlight),
+                    c => c.to_string(),
+                })
+                .collect::<String>();
+
+            println!("\t{}|  {}", row_counter, line);
+            row_counter -= 1;
+        }
 
-        let a = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let b = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        let c = self.unwrap_and_highligth(&mut iter, &indices_to_highlight);
-        println!("\t{}|  {}------------{}------------{}", row_counter, c, b, a);
-        println!("\t   ____________________________");
-        println!("\t    A   B   C    D    E   F   G");
+        println!("\t   {}", "_".repeat(board.ascii_template[0].chars().count() + 1));
+        println!("\t    {}", board.column_labels);
     }
 
-    // TODO This method is probably really inefficient...
-    fn unwrap_and_highligth(&self, either_iter: &mut EitherIter, to_highlight: &[usize]) -> String {
-        // Get the iter out of the Either, or just return the next of the iterator, if no highlighting shall occur
-        let iter = if either_iter.is_right() {
-            either_iter.as_mut().unwrap_right()
-        } else {
-            // Oh dear...
-            return either_iter.as_mut().unwrap_left().next().unwrap().to_string();
-        };
-        let next_element = iter.next().unwrap();
-
-        // The fields getting printed from top to bottom, therefore the indices must start with the last elements of state array
-        let highlight_next_element =
-            to_highlight.contains(&next_element.0.abs_diff(super::representation::constants::FIELD_COUNT - 1));
-
-        if highlight_next_element {
-            EMP.paint(&next_element.1).to_string()
+    /// Returns the state of the field with the specified index as string, painted if it should be highlighted
+    fn paint_point(&self, index: usize, to_highlight: &[usize]) -> String {
+        if to_highlight.contains(&index) {
+            EMP.paint(&self.state[index]).to_string()
         } else {
-            next_element.1.to_string()
+            self.state[index].to_string()
         }
     }
 }
@@ -29,8 +29,26 @@ pub mod game {
         }
     }
 
+    pub mod board;
     pub mod efficient_state;
+    pub mod engine;
+    pub mod flow;
+    pub mod moves;
+    pub mod player;
+    pub mod position;
+    pub mod result;
+    pub mod rules;
     pub mod state;
 
+    pub use flow::Game;
+
     pub type Field = (char, u8);
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub enum GamePhase {
+        Start,
+        Set,
+        MoveAndJump,
+        Terminated,
+    }
 }
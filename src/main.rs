@@ -1,8 +1,18 @@
+use std::process::ExitCode;
+
 use coordination::GameCoordinator;
+use mill::game::result::GameResult;
 
 mod coordination;
 
-fn main() {
+/// The exit code reports the result of the (last) game: 10 if white won, 11 if black won & 12 for draws.
+/// They differ from the codes of failures, like 1 for errors & 101 for panics.
+fn main() -> ExitCode {
     let mut coordinator = GameCoordinator::setup();
-    coordinator.start_game();
+
+    match coordinator.start_session() {
+        GameResult::WhiteWins(_) => ExitCode::from(10),
+        GameResult::BlackWins(_) => ExitCode::from(11),
+        GameResult::Draw(_) => ExitCode::from(12),
+    }
 }